@@ -0,0 +1,608 @@
+//! Conversions to and from IEEE 754 decimal floating point numbers.
+//!
+//! The `decimal32`, `decimal64` and `decimal128` formats are represented by
+//! their bits as `u32`, `u64` and `u128`, in the Binary Integer Decimal (BID)
+//! encoding, as used by Intel's Decimal Floating-Point Math Library. Functions
+//! with `dpd32`, `dpd64` or `dpd128` in their name use the Densely Packed
+//! Decimal (DPD) encoding of these formats instead, as used by IBM POWER and
+//! z/Architecture hardware.
+//!
+//! A finite decimal number is a coefficient of at most 7, 16 or 34 decimal
+//! digits, multiplied by a power of ten. Many values have more than one
+//! representation, such as 1 × 10<sup>2</sup> and 100 × 10<sup>0</sup>.
+//!
+//! - Conversions that can be inexact take a [`Round`] argument. Conversions
+//!   that are always exact do not.
+//! - Exact results use the exponent closest to zero, so integers that fit get
+//!   an exponent of zero.
+//! - Overflow results in infinity or the largest finite value, depending on
+//!   the rounding mode, as specified by IEEE 754.
+//! - Coefficients that are too large for the format (non-canonical encodings)
+//!   are treated as zero.
+//! - `NaN` stays `NaN`, but becomes quiet. The payload is not kept.
+//! - Conversions to integers round according to the given rounding mode, and
+//!   saturate. `NaN` is converted to zero.
+
+use crate::soft;
+use crate::Round;
+use core::cmp::Ordering;
+
+/// Description of a decimal format.
+#[derive(Clone, Copy)]
+struct Format {
+    /// The total number of bits.
+    bits: u32,
+    /// The maximum number of digits of the coefficient.
+    digits: u32,
+    /// The number of bits of the exponent.
+    exponent_bits: u32,
+    /// The exponent bias.
+    bias: i32,
+}
+
+const DECIMAL32: Format = Format { bits: 32, digits: 7, exponent_bits: 8, bias: 101 };
+const DECIMAL64: Format = Format { bits: 64, digits: 16, exponent_bits: 10, bias: 398 };
+const DECIMAL128: Format = Format { bits: 128, digits: 34, exponent_bits: 14, bias: 6176 };
+
+impl Format {
+    /// The number of coefficient bits in the BID encoding, when the
+    /// coefficient is small enough to not need the implicit `100` prefix.
+    fn coefficient_bits(self) -> u32 {
+        self.bits - 1 - self.exponent_bits
+    }
+
+    /// The number of declets in the DPD encoding, each encoding three digits.
+    /// Together with the leading digit, these make up the coefficient.
+    fn declets(self) -> u32 {
+        (self.digits - 1) / 3
+    }
+
+    fn infinity(self, negative: bool) -> u128 {
+        (negative as u128) << (self.bits - 1) | 0b11110 << (self.bits - 6)
+    }
+
+    fn nan(self, negative: bool) -> u128 {
+        (negative as u128) << (self.bits - 1) | 0b11111 << (self.bits - 6)
+    }
+}
+
+/// A decoded decimal number, without its sign.
+enum Decimal {
+    /// The coefficient and the exponent.
+    Finite(u128, i32),
+    Infinity,
+    NaN { signaling: bool, payload: u128 },
+}
+
+fn pow10(n: u32) -> u128 {
+    10u128.pow(n)
+}
+
+/// The number of decimal digits of `c`, or zero if `c` is zero.
+fn digits(c: u128) -> u32 {
+    (0..39).find(|&n| c < pow10(n)).unwrap_or(39)
+}
+
+fn decode_bid(f: Format, x: u128) -> (bool, Decimal) {
+    let negative = x >> (f.bits - 1) & 1 != 0;
+    let t = f.coefficient_bits();
+    let exponent_mask = !(!0 << f.exponent_bits);
+    let (e, c) = if x >> (f.bits - 3) & 3 != 3 {
+        (x >> t & exponent_mask, x & !(!0 << t))
+    } else if x >> (f.bits - 5) & 3 != 3 { // Coefficient with implicit 100 prefix.
+        (x >> (t - 2) & exponent_mask, 0b100 << (t - 2) | x & !(!0 << (t - 2)))
+    } else if x >> (f.bits - 6) & 1 == 0 {
+        return (negative, Decimal::Infinity);
+    } else {
+        return (negative, nan(f, x, decode_bid));
+    };
+    let c = if c < pow10(f.digits) { c } else { 0 }; // Non-canonical coefficients are zero.
+    (negative, Decimal::Finite(c, e as i32 - f.bias))
+}
+
+/// Encodes a coefficient of at most `f.digits` digits and an exponent within
+/// the range of the format.
+fn encode_bid(f: Format, negative: bool, c: u128, q: i32) -> u128 {
+    let sign_bit = (negative as u128) << (f.bits - 1);
+    let t = f.coefficient_bits();
+    let e = (q + f.bias) as u128;
+    if c >> t == 0 {
+        sign_bit | e << t | c
+    } else { // Coefficient with implicit 100 prefix.
+        sign_bit | 3 << (f.bits - 3) | e << (t - 2) | c & !(!0 << (t - 2))
+    }
+}
+
+/// Converts three digits to a declet.
+fn encode_declet(n: u32) -> u32 {
+    let (d1, d2, d3) = (n / 100, n / 10 % 10, n % 10);
+    let (b1, b2, b3) = (d1 & 7, d2 & 7, d3 & 7); // Lower three bits of each digit.
+    match (d1 > 7, d2 > 7, d3 > 7) {
+        (false, false, false) => b1 << 7 | b2 << 4 | b3,
+        (false, false, true) => b1 << 7 | b2 << 4 | 0b1000 | d3 & 1,
+        (false, true, false) => b1 << 7 | (b3 & 6) << 4 | (d2 & 1) << 4 | 0b1010 | d3 & 1,
+        (true, false, false) => (b3 & 6) << 7 | (d1 & 1) << 7 | b2 << 4 | 0b1100 | d3 & 1,
+        (true, true, false) => (b3 & 6) << 7 | (d1 & 1) << 7 | (d2 & 1) << 4 | 0b1110 | d3 & 1,
+        (true, false, true) => (b2 & 6) << 7 | (d1 & 1) << 7 | 0b10_0000 | (d2 & 1) << 4 | 0b1110 | d3 & 1,
+        (false, true, true) => b1 << 7 | 0b100_0000 | (d2 & 1) << 4 | 0b1110 | d3 & 1,
+        (true, true, true) => (d1 & 1) << 7 | 0b110_0000 | (d2 & 1) << 4 | 0b1110 | d3 & 1,
+    }
+}
+
+/// Converts a declet to three digits. Non-canonical declets are accepted.
+fn decode_declet(x: u32) -> u32 {
+    let (pqr, stu, wxy) = (x >> 7 & 7, x >> 4 & 7, x & 7);
+    let (r, u, y) = (x >> 7 & 1, x >> 4 & 1, x & 1);
+    let (pq, st) = (pqr & 6, stu & 6);
+    let (d1, d2, d3) = if x & 8 == 0 {
+        (pqr, stu, wxy)
+    } else {
+        match (wxy >> 1, st >> 1) {
+            (0b00, _) => (pqr, stu, 8 | y),
+            (0b01, _) => (pqr, 8 | u, st | y),
+            (0b10, _) => (8 | r, stu, pq | y),
+            (_, 0b00) => (8 | r, 8 | u, pq | y),
+            (_, 0b01) => (8 | r, pq | u, 8 | y),
+            (_, 0b10) => (pqr, 8 | u, 8 | y),
+            _ => (8 | r, 8 | u, 8 | y),
+        }
+    };
+    d1 * 100 + d2 * 10 + d3
+}
+
+fn decode_dpd(f: Format, x: u128) -> (bool, Decimal) {
+    let negative = x >> (f.bits - 1) & 1 != 0;
+    let w = f.exponent_bits - 2; // Bits of the exponent continuation field.
+    let t = 10 * f.declets(); // Bits of the trailing significand field.
+    let g = (x >> (f.bits - 6) & 31) as u32; // Combination field.
+    let (e, d) = if g >> 3 != 3 {
+        (g >> 3, g & 7)
+    } else if g >> 1 & 3 != 3 {
+        (g >> 1 & 3, 8 | g & 1)
+    } else if g & 1 == 0 {
+        return (negative, Decimal::Infinity);
+    } else {
+        return (negative, nan(f, x, decode_dpd));
+    };
+    let e = (e as u128) << w | x >> t & !(!0 << w);
+    let c = (0..f.declets()).rev().fold(d as u128, |c, i| c * 1000 + decode_declet((x >> (10 * i) & 1023) as u32) as u128);
+    (negative, Decimal::Finite(c, e as i32 - f.bias))
+}
+
+/// Encodes a coefficient of at most `f.digits` digits and an exponent within
+/// the range of the format.
+fn encode_dpd(f: Format, negative: bool, c: u128, q: i32) -> u128 {
+    let sign_bit = (negative as u128) << (f.bits - 1);
+    let w = f.exponent_bits - 2; // Bits of the exponent continuation field.
+    let t = 10 * f.declets(); // Bits of the trailing significand field.
+    let e = (q + f.bias) as u128;
+    let d = c / pow10(3 * f.declets()); // Leading digit.
+    let g = if d < 8 { (e >> w) << 3 | d } else { 0b11000 | (e >> w) << 1 | d & 1 }; // Combination field.
+    let trailing = (0..f.declets()).fold(0, |x, i| x | (encode_declet((c / pow10(3 * i) % 1000) as u32) as u128) << (10 * i));
+    sign_bit | g << (f.bits - 6) | (e & !(!0 << w)) << t | trailing
+}
+
+/// Decodes the payload of a NaN, which is stored in the trailing significand
+/// field like a coefficient. Non-canonical payloads are zero.
+fn nan(f: Format, x: u128, decode: fn(Format, u128) -> (bool, Decimal)) -> Decimal {
+    let payload = match decode(f, x & !(!0 << (10 * f.declets()))) {
+        (_, Decimal::Finite(c, _)) if c < pow10(3 * f.declets()) => c,
+        _ => 0,
+    };
+    Decimal::NaN { signaling: x >> (f.bits - 7) & 1 != 0, payload }
+}
+
+/// Encodes any decoded number, using `encode` for the coefficient.
+fn encode(f: Format, (negative, d): (bool, Decimal), encode: fn(Format, bool, u128, i32) -> u128) -> u128 {
+    match d {
+        Decimal::Finite(c, q) => encode(f, negative, c, q),
+        Decimal::Infinity => f.infinity(negative),
+        Decimal::NaN { signaling, payload } => f.nan(negative) | (signaling as u128) << (f.bits - 7) | encode(f, false, payload, -f.bias),
+    }
+}
+
+/// Rounds an integer to the format, and encodes it using `encode`.
+fn round_and_encode(f: Format, negative: bool, c: u128, round: Round, encode: fn(Format, bool, u128, i32) -> u128) -> u128 {
+    let d = digits(c).saturating_sub(f.digits); // Number of digits to drop.
+    if d == 0 {
+        return encode(f, negative, c, 0);
+    }
+    let (p, t, r) = (pow10(d), c / pow10(d), c % pow10(d));
+    let t = t + round.round_up(negative, t & 1 == 1, (2 * r).cmp(&p), r != 0) as u128;
+    if t == pow10(f.digits) { // Rounding added a digit.
+        encode(f, negative, t / 10, d as i32 + 1)
+    } else {
+        encode(f, negative, t, d as i32)
+    }
+}
+
+/// Rounds a decimal number to an integer, and returns its sign and its
+/// magnitude, saturated to `u128::MAX`.
+fn to_integer(d: (bool, Decimal), round: Round) -> (bool, u128) {
+    match d {
+        (negative, Decimal::Finite(c, q)) => {
+            if c == 0 {
+                (negative, 0)
+            } else if q > 38 {
+                (negative, u128::MAX)
+            } else if q >= 0 {
+                (negative, c.saturating_mul(pow10(q as u32)))
+            } else {
+                let (t, half, inexact) = if q < -38 { // Less than half.
+                    (0, Ordering::Less, true)
+                } else {
+                    let (p, r) = (pow10(-q as u32), c % pow10(-q as u32));
+                    (c / p, (2 * r).cmp(&p), r != 0)
+                };
+                (negative, t + round.round_up(negative, t & 1 == 1, half, inexact) as u128)
+            }
+        }
+        (negative, Decimal::Infinity) => (negative, u128::MAX),
+        (_, Decimal::NaN { .. }) => (false, 0),
+    }
+}
+
+/// A non-negative integer of up to 1536 bits, which is enough for any value
+/// of an `f64` or a `decimal64`, with some room for shifting.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Big([u64; 24]);
+
+impl Big {
+    fn new(x: u64) -> Big {
+        let mut b = Big([0; 24]);
+        b.0[0] = x;
+        b
+    }
+
+    fn bits(&self) -> u32 {
+        match self.0.iter().rposition(|&x| x != 0) {
+            Some(i) => 64 * i as u32 + 64 - self.0[i].leading_zeros(),
+            None => 0,
+        }
+    }
+
+    fn trailing_zeros(&self) -> u32 {
+        match self.0.iter().position(|&x| x != 0) {
+            Some(i) => 64 * i as u32 + self.0[i].trailing_zeros(),
+            None => 64 * 24,
+        }
+    }
+
+    fn mul_small(&mut self, m: u64) {
+        let mut carry = 0;
+        for x in &mut self.0 {
+            let y = *x as u128 * m as u128 + carry;
+            *x = y as u64;
+            carry = y >> 64;
+        }
+    }
+
+    fn mul_pow5(&mut self, mut n: u32) {
+        while n > 0 {
+            let k = n.min(27); // 5^27 fits in a u64.
+            self.mul_small(5u64.pow(k));
+            n -= k;
+        }
+    }
+
+    fn shl(&mut self, n: u32) {
+        let (limbs, bits) = ((n / 64) as usize, n % 64);
+        for i in (0..24).rev() {
+            let hi = if i >= limbs { self.0[i - limbs] } else { 0 };
+            let lo = if i > limbs { self.0[i - limbs - 1] } else { 0 };
+            self.0[i] = if bits == 0 { hi } else { hi << bits | lo >> (64 - bits) };
+        }
+    }
+
+    fn shr(&mut self, n: u32) {
+        let (limbs, bits) = ((n / 64) as usize, n % 64);
+        for i in 0..24 {
+            let lo = if i + limbs < 24 { self.0[i + limbs] } else { 0 };
+            let hi = if i + limbs + 1 < 24 { self.0[i + limbs + 1] } else { 0 };
+            self.0[i] = if bits == 0 { lo } else { lo >> bits | hi << (64 - bits) };
+        }
+    }
+
+    fn sub(&mut self, other: &Big) {
+        let mut borrow = false;
+        for (x, &y) in self.0.iter_mut().zip(&other.0) {
+            let (a, b) = x.overflowing_sub(y);
+            let (a, c) = a.overflowing_sub(borrow as u64);
+            *x = a;
+            borrow = b || c;
+        }
+    }
+
+    fn cmp(&self, other: &Big) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+
+    /// Divides by `den`, leaving the remainder. The quotient must fit in a
+    /// `u64`.
+    fn div_rem(&mut self, den: &Big) -> u64 {
+        let mut d = *den;
+        d.shl(63);
+        let mut q = 0;
+        for i in (0..64).rev() {
+            if self.cmp(&d) != Ordering::Less {
+                self.sub(&d);
+                q |= 1 << i;
+            }
+            d.shr(1);
+        }
+        q
+    }
+}
+
+/// Converts the bits of an `f64` to a `decimal64`.
+pub fn f64_to_decimal64(f: u64, round: Round) -> u64 {
+    let negative = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let (m, e) = if a == 0 { // Zero
+        return encode_bid(DECIMAL64, negative, 0, 0) as u64;
+    } else if a < 1 << 52 { // Subnormal
+        (a, -1074)
+    } else if a < 2047 << 52 { // Normal
+        (a & !0 >> 12 | 1 << 52, (a >> 52) as i32 - 1075)
+    } else if a == 2047 << 52 { // Infinity
+        return DECIMAL64.infinity(negative) as u64;
+    } else { // NaN
+        return DECIMAL64.nan(negative) as u64;
+    };
+    let n = 64 - m.leading_zeros() as i32; // Number of bits.
+    let mut q = (((n - 1 + e) * 78913) >> 18) - 15; // Exponent for 16 digits, possibly one too low.
+    loop {
+        // m × 2^e / 10^q = (m × 2^(e - q) × 5^-q) / 1.
+        let mut num = Big::new(m);
+        let mut den = Big::new(1);
+        if q < 0 { num.mul_pow5(-q as u32) } else { den.mul_pow5(q as u32) }
+        if e - q > 0 { num.shl((e - q) as u32) } else { den.shl((q - e) as u32) }
+        let c = num.div_rem(&den);
+        if c >= pow10(16) as u64 {
+            q += 1;
+            continue;
+        }
+        num.shl(1);
+        let half = num.cmp(&den);
+        let inexact = num != Big::new(0);
+        let mut c = (c + round.round_up(negative, c & 1 == 1, half, inexact) as u64) as u128;
+        if c == pow10(16) { // Rounding added a digit.
+            c /= 10;
+            q += 1;
+        }
+        if !inexact {
+            while q < 0 && c.is_multiple_of(10) { // Use the exponent closest to zero.
+                c /= 10;
+                q += 1;
+            }
+        }
+        return encode_bid(DECIMAL64, negative, c, q) as u64;
+    }
+}
+
+/// Converts a `decimal64` to the bits of an `f64`.
+pub fn decimal64_to_f64(x: u64, round: Round) -> u64 {
+    let (negative, c, q) = match decode_bid(DECIMAL64, x.into()) {
+        (negative, Decimal::Finite(c, q)) => (negative, c as u64, q),
+        (negative, Decimal::Infinity) => return 2047 << 52 | (negative as u64) << 63,
+        (negative, Decimal::NaN { .. }) => return 0x7FF8 << 48 | (negative as u64) << 63,
+    };
+    if c == 0 {
+        return (negative as u64) << 63;
+    }
+    let mut num = Big::new(c);
+    if q >= 0 {
+        // c × 5^q × 2^q: take the highest 64 bits, and whether any bits remain.
+        num.mul_pow5(q as u32);
+        let s = num.bits().saturating_sub(64);
+        let sticky = num.trailing_zeros() < s;
+        num.shr(s);
+        soft::binary_to_f64(negative, num.0[0], q + s as i32, sticky, round)
+    } else {
+        // c / 10^-q: shift c such that the quotient has 63 or 64 bits.
+        let mut den = Big::new(1);
+        den.mul_pow5(-q as u32);
+        den.shl(-q as u32);
+        let s = den.bits() + 63 - num.bits();
+        num.shl(s);
+        let m = num.div_rem(&den);
+        soft::binary_to_f64(negative, m, -(s as i32), num != Big::new(0), round)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($name:ident $t:ident => $d:ident $format:ident $encode:ident $doc:literal) => {
+        #[doc = concat!("Converts a `", stringify!($t), "` to ", $doc, ". Always exact.")]
+        pub fn $name(x: $t) -> $d {
+            let (negative, a) = impl_from_int!(@abs $t x);
+            $encode($format, negative, a.into(), 0) as $d
+        }
+    };
+    ($name:ident $t:ident => $d:ident $format:ident $encode:ident $doc:literal, round) => {
+        #[doc = concat!("Converts a `", stringify!($t), "` to ", $doc, ".")]
+        pub fn $name(x: $t, round: Round) -> $d {
+            let (negative, a) = impl_from_int!(@abs $t x);
+            round_and_encode($format, negative, a.into(), round, $encode) as $d
+        }
+    };
+    (@abs u8 $x:ident) => { (false, $x) };
+    (@abs u16 $x:ident) => { (false, $x) };
+    (@abs u32 $x:ident) => { (false, $x) };
+    (@abs u64 $x:ident) => { (false, $x) };
+    (@abs u128 $x:ident) => { (false, $x) };
+    (@abs $t:ident $x:ident) => { ($x < 0, $x.unsigned_abs()) };
+}
+
+macro_rules! impl_to_int {
+    ($name:ident $d:ident $format:ident $decode:ident $doc:literal => $t:ident) => {
+        #[doc = concat!("Converts ", $doc, " to a `", stringify!($t), "`.")]
+        pub fn $name(x: $d, round: Round) -> $t {
+            let (negative, a) = to_integer($decode($format, x.into()), round);
+            impl_to_int!(@saturate $t negative a)
+        }
+    };
+    (@saturate u8 $negative:ident $a:ident) => { if $negative { 0 } else { $a.min(u8::MAX.into()) as u8 } };
+    (@saturate u16 $negative:ident $a:ident) => { if $negative { 0 } else { $a.min(u16::MAX.into()) as u16 } };
+    (@saturate u32 $negative:ident $a:ident) => { if $negative { 0 } else { $a.min(u32::MAX.into()) as u32 } };
+    (@saturate u64 $negative:ident $a:ident) => { if $negative { 0 } else { $a.min(u64::MAX.into()) as u64 } };
+    (@saturate u128 $negative:ident $a:ident) => { if $negative { 0 } else { $a } };
+    (@saturate $t:ident $negative:ident $a:ident) => {
+        if $negative {
+            ($a.min($t::MIN.unsigned_abs() as u128) as $t).wrapping_neg()
+        } else {
+            $a.min($t::MAX as u128) as $t
+        }
+    };
+}
+
+macro_rules! impl_recode {
+    ($name:ident $d:ident $format:ident $decode:ident $encode:ident $doc:literal) => {
+        #[doc = concat!("Converts ", $doc, ". Always exact.")]
+        ///
+        /// Signaling NaNs stay signaling, and NaN payloads are kept.
+        pub fn $name(x: $d) -> $d {
+            encode($format, $decode($format, x.into()), $encode) as $d
+        }
+    };
+}
+
+impl_recode!(decimal32_to_dpd32 u32 DECIMAL32 decode_bid encode_dpd "a `decimal32` in the BID encoding to the DPD encoding");
+impl_recode!(dpd32_to_decimal32 u32 DECIMAL32 decode_dpd encode_bid "a `decimal32` in the DPD encoding to the BID encoding");
+
+impl_recode!(decimal64_to_dpd64 u64 DECIMAL64 decode_bid encode_dpd "a `decimal64` in the BID encoding to the DPD encoding");
+impl_recode!(dpd64_to_decimal64 u64 DECIMAL64 decode_dpd encode_bid "a `decimal64` in the DPD encoding to the BID encoding");
+
+impl_recode!(decimal128_to_dpd128 u128 DECIMAL128 decode_bid encode_dpd "a `decimal128` in the BID encoding to the DPD encoding");
+impl_recode!(dpd128_to_decimal128 u128 DECIMAL128 decode_dpd encode_bid "a `decimal128` in the DPD encoding to the BID encoding");
+
+impl_from_int!(u8_to_decimal32 u8 => u32 DECIMAL32 encode_bid "a `decimal32`");
+impl_from_int!(u16_to_decimal32 u16 => u32 DECIMAL32 encode_bid "a `decimal32`");
+impl_from_int!(u32_to_decimal32 u32 => u32 DECIMAL32 encode_bid "a `decimal32`", round);
+impl_from_int!(u64_to_decimal32 u64 => u32 DECIMAL32 encode_bid "a `decimal32`", round);
+impl_from_int!(u128_to_decimal32 u128 => u32 DECIMAL32 encode_bid "a `decimal32`", round);
+impl_from_int!(i8_to_decimal32 i8 => u32 DECIMAL32 encode_bid "a `decimal32`");
+impl_from_int!(i16_to_decimal32 i16 => u32 DECIMAL32 encode_bid "a `decimal32`");
+impl_from_int!(i32_to_decimal32 i32 => u32 DECIMAL32 encode_bid "a `decimal32`", round);
+impl_from_int!(i64_to_decimal32 i64 => u32 DECIMAL32 encode_bid "a `decimal32`", round);
+impl_from_int!(i128_to_decimal32 i128 => u32 DECIMAL32 encode_bid "a `decimal32`", round);
+
+impl_from_int!(u8_to_dpd32 u8 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding");
+impl_from_int!(u16_to_dpd32 u16 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding");
+impl_from_int!(u32_to_dpd32 u32 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding", round);
+impl_from_int!(u64_to_dpd32 u64 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding", round);
+impl_from_int!(u128_to_dpd32 u128 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding", round);
+impl_from_int!(i8_to_dpd32 i8 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding");
+impl_from_int!(i16_to_dpd32 i16 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding");
+impl_from_int!(i32_to_dpd32 i32 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding", round);
+impl_from_int!(i64_to_dpd32 i64 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding", round);
+impl_from_int!(i128_to_dpd32 i128 => u32 DECIMAL32 encode_dpd "a `decimal32` in the DPD encoding", round);
+
+impl_from_int!(u8_to_decimal64 u8 => u64 DECIMAL64 encode_bid "a `decimal64`");
+impl_from_int!(u16_to_decimal64 u16 => u64 DECIMAL64 encode_bid "a `decimal64`");
+impl_from_int!(u32_to_decimal64 u32 => u64 DECIMAL64 encode_bid "a `decimal64`");
+impl_from_int!(u64_to_decimal64 u64 => u64 DECIMAL64 encode_bid "a `decimal64`", round);
+impl_from_int!(u128_to_decimal64 u128 => u64 DECIMAL64 encode_bid "a `decimal64`", round);
+impl_from_int!(i8_to_decimal64 i8 => u64 DECIMAL64 encode_bid "a `decimal64`");
+impl_from_int!(i16_to_decimal64 i16 => u64 DECIMAL64 encode_bid "a `decimal64`");
+impl_from_int!(i32_to_decimal64 i32 => u64 DECIMAL64 encode_bid "a `decimal64`");
+impl_from_int!(i64_to_decimal64 i64 => u64 DECIMAL64 encode_bid "a `decimal64`", round);
+impl_from_int!(i128_to_decimal64 i128 => u64 DECIMAL64 encode_bid "a `decimal64`", round);
+
+impl_from_int!(u8_to_dpd64 u8 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding");
+impl_from_int!(u16_to_dpd64 u16 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding");
+impl_from_int!(u32_to_dpd64 u32 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding");
+impl_from_int!(u64_to_dpd64 u64 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding", round);
+impl_from_int!(u128_to_dpd64 u128 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding", round);
+impl_from_int!(i8_to_dpd64 i8 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding");
+impl_from_int!(i16_to_dpd64 i16 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding");
+impl_from_int!(i32_to_dpd64 i32 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding");
+impl_from_int!(i64_to_dpd64 i64 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding", round);
+impl_from_int!(i128_to_dpd64 i128 => u64 DECIMAL64 encode_dpd "a `decimal64` in the DPD encoding", round);
+
+impl_from_int!(u8_to_decimal128 u8 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(u16_to_decimal128 u16 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(u32_to_decimal128 u32 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(u64_to_decimal128 u64 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(u128_to_decimal128 u128 => u128 DECIMAL128 encode_bid "a `decimal128`", round);
+impl_from_int!(i8_to_decimal128 i8 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(i16_to_decimal128 i16 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(i32_to_decimal128 i32 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(i64_to_decimal128 i64 => u128 DECIMAL128 encode_bid "a `decimal128`");
+impl_from_int!(i128_to_decimal128 i128 => u128 DECIMAL128 encode_bid "a `decimal128`", round);
+
+impl_from_int!(u8_to_dpd128 u8 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(u16_to_dpd128 u16 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(u32_to_dpd128 u32 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(u64_to_dpd128 u64 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(u128_to_dpd128 u128 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding", round);
+impl_from_int!(i8_to_dpd128 i8 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(i16_to_dpd128 i16 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(i32_to_dpd128 i32 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(i64_to_dpd128 i64 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding");
+impl_from_int!(i128_to_dpd128 i128 => u128 DECIMAL128 encode_dpd "a `decimal128` in the DPD encoding", round);
+
+impl_to_int!(decimal32_to_u8 u32 DECIMAL32 decode_bid "a `decimal32`" => u8);
+impl_to_int!(decimal32_to_u16 u32 DECIMAL32 decode_bid "a `decimal32`" => u16);
+impl_to_int!(decimal32_to_u32 u32 DECIMAL32 decode_bid "a `decimal32`" => u32);
+impl_to_int!(decimal32_to_u64 u32 DECIMAL32 decode_bid "a `decimal32`" => u64);
+impl_to_int!(decimal32_to_u128 u32 DECIMAL32 decode_bid "a `decimal32`" => u128);
+impl_to_int!(decimal32_to_i8 u32 DECIMAL32 decode_bid "a `decimal32`" => i8);
+impl_to_int!(decimal32_to_i16 u32 DECIMAL32 decode_bid "a `decimal32`" => i16);
+impl_to_int!(decimal32_to_i32 u32 DECIMAL32 decode_bid "a `decimal32`" => i32);
+impl_to_int!(decimal32_to_i64 u32 DECIMAL32 decode_bid "a `decimal32`" => i64);
+impl_to_int!(decimal32_to_i128 u32 DECIMAL32 decode_bid "a `decimal32`" => i128);
+
+impl_to_int!(dpd32_to_u8 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => u8);
+impl_to_int!(dpd32_to_u16 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => u16);
+impl_to_int!(dpd32_to_u32 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => u32);
+impl_to_int!(dpd32_to_u64 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => u64);
+impl_to_int!(dpd32_to_u128 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => u128);
+impl_to_int!(dpd32_to_i8 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => i8);
+impl_to_int!(dpd32_to_i16 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => i16);
+impl_to_int!(dpd32_to_i32 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => i32);
+impl_to_int!(dpd32_to_i64 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => i64);
+impl_to_int!(dpd32_to_i128 u32 DECIMAL32 decode_dpd "a `decimal32` in the DPD encoding" => i128);
+
+impl_to_int!(decimal64_to_u8 u64 DECIMAL64 decode_bid "a `decimal64`" => u8);
+impl_to_int!(decimal64_to_u16 u64 DECIMAL64 decode_bid "a `decimal64`" => u16);
+impl_to_int!(decimal64_to_u32 u64 DECIMAL64 decode_bid "a `decimal64`" => u32);
+impl_to_int!(decimal64_to_u64 u64 DECIMAL64 decode_bid "a `decimal64`" => u64);
+impl_to_int!(decimal64_to_u128 u64 DECIMAL64 decode_bid "a `decimal64`" => u128);
+impl_to_int!(decimal64_to_i8 u64 DECIMAL64 decode_bid "a `decimal64`" => i8);
+impl_to_int!(decimal64_to_i16 u64 DECIMAL64 decode_bid "a `decimal64`" => i16);
+impl_to_int!(decimal64_to_i32 u64 DECIMAL64 decode_bid "a `decimal64`" => i32);
+impl_to_int!(decimal64_to_i64 u64 DECIMAL64 decode_bid "a `decimal64`" => i64);
+impl_to_int!(decimal64_to_i128 u64 DECIMAL64 decode_bid "a `decimal64`" => i128);
+
+impl_to_int!(dpd64_to_u8 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => u8);
+impl_to_int!(dpd64_to_u16 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => u16);
+impl_to_int!(dpd64_to_u32 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => u32);
+impl_to_int!(dpd64_to_u64 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => u64);
+impl_to_int!(dpd64_to_u128 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => u128);
+impl_to_int!(dpd64_to_i8 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => i8);
+impl_to_int!(dpd64_to_i16 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => i16);
+impl_to_int!(dpd64_to_i32 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => i32);
+impl_to_int!(dpd64_to_i64 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => i64);
+impl_to_int!(dpd64_to_i128 u64 DECIMAL64 decode_dpd "a `decimal64` in the DPD encoding" => i128);
+
+impl_to_int!(decimal128_to_u8 u128 DECIMAL128 decode_bid "a `decimal128`" => u8);
+impl_to_int!(decimal128_to_u16 u128 DECIMAL128 decode_bid "a `decimal128`" => u16);
+impl_to_int!(decimal128_to_u32 u128 DECIMAL128 decode_bid "a `decimal128`" => u32);
+impl_to_int!(decimal128_to_u64 u128 DECIMAL128 decode_bid "a `decimal128`" => u64);
+impl_to_int!(decimal128_to_u128 u128 DECIMAL128 decode_bid "a `decimal128`" => u128);
+impl_to_int!(decimal128_to_i8 u128 DECIMAL128 decode_bid "a `decimal128`" => i8);
+impl_to_int!(decimal128_to_i16 u128 DECIMAL128 decode_bid "a `decimal128`" => i16);
+impl_to_int!(decimal128_to_i32 u128 DECIMAL128 decode_bid "a `decimal128`" => i32);
+impl_to_int!(decimal128_to_i64 u128 DECIMAL128 decode_bid "a `decimal128`" => i64);
+impl_to_int!(decimal128_to_i128 u128 DECIMAL128 decode_bid "a `decimal128`" => i128);
+
+impl_to_int!(dpd128_to_u8 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => u8);
+impl_to_int!(dpd128_to_u16 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => u16);
+impl_to_int!(dpd128_to_u32 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => u32);
+impl_to_int!(dpd128_to_u64 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => u64);
+impl_to_int!(dpd128_to_u128 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => u128);
+impl_to_int!(dpd128_to_i8 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => i8);
+impl_to_int!(dpd128_to_i16 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => i16);
+impl_to_int!(dpd128_to_i32 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => i32);
+impl_to_int!(dpd128_to_i64 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => i64);
+impl_to_int!(dpd128_to_i128 u128 DECIMAL128 decode_dpd "a `decimal128` in the DPD encoding" => i128);
@@ -2,6 +2,14 @@
 //!
 //! These are implemented using a mix of native floating point instructions (if
 //! available) and (partial) soft implementations.
+//!
+//! The [`slice`](mod@slice) module provides the same conversions for whole slices at
+//! once.
+
+pub mod slice;
+
+use crate::Round;
+use core::cmp::Ordering;
 
 #[allow(unused_macros)]
 macro_rules! impl_native {
@@ -262,3 +270,360 @@ group! {
     impl_soft!(u128_to_f64 u128 f64);
     impl_soft!(i128_to_f64 i128 f64);
 }
+
+/// Converts to bfloat16 (represented as `u16`), rounding to the nearest value
+/// and breaking ties to even.
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx512bf16",
+    target_feature = "avx512vl",
+))]
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_bf16(x: f32) -> u16 {
+    if x.to_bits() & 255 << 23 == 0 {
+        // The instruction flushes subnormals to zero.
+        return crate::soft::f32_to_bf16(x.to_bits());
+    }
+    let y: f32;
+    unsafe {
+        core::arch::asm!("vcvtneps2bf16 {0}, {1}", lateout(xmm_reg) y, in(xmm_reg) x, options(pure, nomem, nostack));
+    }
+    y.to_bits() as u16
+}
+
+/// Converts to bfloat16 (represented as `u16`), rounding to the nearest value
+/// and breaking ties to even.
+#[cfg(all(target_arch = "aarch64", target_feature = "bf16"))]
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_bf16(x: f32) -> u16 {
+    let y: f32;
+    unsafe {
+        core::arch::asm!("bfcvt {0:h}, {1:s}", lateout(vreg) y, in(vreg) x, options(pure, nomem, nostack));
+    }
+    y.to_bits() as u16
+}
+
+/// Converts to bfloat16 (represented as `u16`), rounding to the nearest value
+/// and breaking ties to even.
+#[cfg(not(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "avx512bf16",
+        target_feature = "avx512vl",
+    ),
+    all(target_arch = "aarch64", target_feature = "bf16"),
+)))]
+#[inline]
+pub fn f32_to_bf16(x: f32) -> u16 {
+    crate::soft::f32_to_bf16(x.to_bits())
+}
+
+/// Converts from bfloat16 (represented as `u16`). This is always exact.
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_f32(x: u16) -> f32 {
+    f32::from_bits(crate::soft::bf16_to_f32(x))
+}
+
+// Narrowing with directed rounding: convert natively (rounding to nearest) and
+// then step one value towards the desired direction if we went the other way.
+#[cfg(any(
+    target_arch = "aarch64",
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_feature = "vfp2",
+))]
+group! {
+    #[cfg_attr(not(noinline), inline)]
+    pub fn f64_to_f32_truncate(x: f64) -> f32 {
+        let y = x as f32;
+        if (y as f64).abs() > x.abs() { f32::from_bits(y.to_bits() - 1) } else { y }
+    }
+
+    #[cfg_attr(not(noinline), inline)]
+    pub fn f64_to_f32_ceil(x: f64) -> f32 {
+        let y = x as f32;
+        if (y as f64) < x {
+            if y.is_sign_negative() { f32::from_bits(y.to_bits() - 1) } else { f32::from_bits(y.to_bits() + 1) }
+        } else {
+            y
+        }
+    }
+
+    #[cfg_attr(not(noinline), inline)]
+    pub fn f64_to_f32_floor(x: f64) -> f32 {
+        let y = x as f32;
+        if (y as f64) > x {
+            if y.is_sign_negative() { f32::from_bits(y.to_bits() + 1) } else { f32::from_bits(y.to_bits() - 1) }
+        } else {
+            y
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_arch = "aarch64",
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_feature = "vfp2",
+)))]
+group! {
+    impl_soft!(f64_to_f32_truncate f64 f32);
+    impl_soft!(f64_to_f32_ceil f64 f32);
+    impl_soft!(f64_to_f32_floor f64 f32);
+}
+
+// There is no native f128 support, so these take the bits as `u128`.
+impl_soft!(f128_to_f64_truncate u128 f64);
+impl_soft!(f128_to_f64_ceil u128 f64);
+impl_soft!(f128_to_f64_floor u128 f64);
+
+// Conversions to and from f16, which is represented as `u16`.
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "f16c"))]
+group! {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    macro_rules! impl_f16c {
+        ($name:tt $rounding:tt) => {
+            #[cfg_attr(not(noinline), inline)]
+            pub fn $name(x: f32) -> u16 {
+                unsafe { _mm_cvtsi128_si32(_mm_cvtps_ph::<$rounding>(_mm_set_ss(x))) as u16 }
+            }
+        };
+    }
+
+    impl_f16c!(f32_to_f16 _MM_FROUND_TO_NEAREST_INT);
+    impl_f16c!(f32_to_f16_truncate _MM_FROUND_TO_ZERO);
+    impl_f16c!(f32_to_f16_ceil _MM_FROUND_TO_POS_INF);
+    impl_f16c!(f32_to_f16_floor _MM_FROUND_TO_NEG_INF);
+
+    #[cfg_attr(not(noinline), inline)]
+    pub fn f16_to_f32(x: u16) -> f32 {
+        unsafe { _mm_cvtss_f32(_mm_cvtph_ps(_mm_cvtsi32_si128(x as i32))) }
+    }
+
+    #[cfg_attr(not(noinline), inline)]
+    pub fn f16_to_f64(x: u16) -> f64 {
+        f16_to_f32(x) as f64 // Both steps are lossless.
+    }
+
+    impl_soft!(f64_to_f16 f64 u16); // Going through f32 would round twice.
+}
+
+#[cfg(target_arch = "aarch64")]
+group! {
+    macro_rules! impl_fcvt {
+        ($name:tt $from:tt $to:tt $x:tt $y:tt $convert:expr, $instr:tt) => {
+            #[cfg_attr(not(noinline), inline)]
+            pub fn $name(x: $from) -> $to {
+                let x: $x = $convert(x);
+                let y: $y;
+                unsafe {
+                    core::arch::asm!($instr, lateout(vreg) y, in(vreg) x, options(pure, nomem, nostack));
+                }
+                impl_fcvt!(@result $to y)
+            }
+        };
+        (@result u16 $y:tt) => { $y.to_bits() as u16 };
+        (@result $_:tt $y:tt) => { $y };
+    }
+
+    // The f16 half of the register is moved through the low bits of an f32.
+    impl_fcvt!(f32_to_f16 f32 u16 f32 f32 |x| x, "fcvt {0:h}, {1:s}");
+    impl_fcvt!(f64_to_f16 f64 u16 f64 f32 |x| x, "fcvt {0:h}, {1:d}");
+    impl_fcvt!(f16_to_f32 u16 f32 f32 f32 |x| f32::from_bits(x as u32), "fcvt {0:s}, {1:h}");
+    impl_fcvt!(f16_to_f64 u16 f64 f32 f64 |x| f32::from_bits(x as u32), "fcvt {0:d}, {1:h}");
+    impl_soft!(f32_to_f16_truncate f32 u16);
+    impl_soft!(f32_to_f16_ceil f32 u16);
+    impl_soft!(f32_to_f16_floor f32 u16);
+}
+
+#[cfg(not(any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "f16c"),
+    target_arch = "aarch64",
+)))]
+group! {
+    impl_soft!(f32_to_f16 f32 u16);
+    impl_soft!(f64_to_f16 f64 u16);
+    impl_soft!(f16_to_f32 u16 f32);
+    impl_soft!(f16_to_f64 u16 f64);
+    impl_soft!(f32_to_f16_truncate f32 u16);
+    impl_soft!(f32_to_f16_ceil f32 u16);
+    impl_soft!(f32_to_f16_floor f32 u16);
+}
+
+// Scaling by a power of two is exact as long as the result stays normal and
+// finite, so only the conversion from the integer rounds.
+
+macro_rules! impl_scaled {
+    ($name:ident $int:tt $float:tt $bits:tt $bias:literal) => {
+        #[cfg_attr(not(noinline), inline)]
+        pub fn $name(x: $int, exp: i32) -> $float {
+            if exp > -$bias && exp <= $bias - $int::BITS as i32 {
+                x as $float * $float::from_bits((($bias + exp) as $bits) << ($float::MANTISSA_DIGITS - 1))
+            } else {
+                $float::from_bits(crate::soft::$name(x, exp))
+            }
+        }
+    };
+}
+
+impl_scaled!(u8_to_f32_scaled u8 f32 u32 127);
+impl_scaled!(i8_to_f32_scaled i8 f32 u32 127);
+impl_scaled!(u16_to_f32_scaled u16 f32 u32 127);
+impl_scaled!(i16_to_f32_scaled i16 f32 u32 127);
+impl_scaled!(u32_to_f32_scaled u32 f32 u32 127);
+impl_scaled!(i32_to_f32_scaled i32 f32 u32 127);
+impl_scaled!(u64_to_f32_scaled u64 f32 u32 127);
+impl_scaled!(i64_to_f32_scaled i64 f32 u32 127);
+impl_scaled!(i128_to_f32_scaled i128 f32 u32 127);
+impl_scaled!(u8_to_f64_scaled u8 f64 u64 1023);
+impl_scaled!(i8_to_f64_scaled i8 f64 u64 1023);
+impl_scaled!(u16_to_f64_scaled u16 f64 u64 1023);
+impl_scaled!(i16_to_f64_scaled i16 f64 u64 1023);
+impl_scaled!(u32_to_f64_scaled u32 f64 u64 1023);
+impl_scaled!(i32_to_f64_scaled i32 f64 u64 1023);
+impl_scaled!(u64_to_f64_scaled u64 f64 u64 1023);
+impl_scaled!(i64_to_f64_scaled i64 f64 u64 1023);
+impl_scaled!(u128_to_f64_scaled u128 f64 u64 1023);
+impl_scaled!(i128_to_f64_scaled i128 f64 u64 1023);
+
+// Rounding a u128 to f32 before scaling could overflow, so use the soft implementation.
+
+/// Soft implementation.
+#[inline]
+pub fn u128_to_f32_scaled(x: u128, exp: i32) -> f32 {
+    f32::from_bits(crate::soft::u128_to_f32_scaled(x, exp))
+}
+
+// Conversions between fixed point numbers and floats. A fixed point number
+// is an integer scaled by a power of two, so converting it to a float is a
+// scaled conversion. In the other direction, scaling by a power of two is
+// exact as long as the result stays normal, so only the conversion to the
+// integer rounds.
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_fixed_to_f32(x: u16, frac: u32) -> f32 {
+    u16_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_fixed_to_f32(x: i16, frac: u32) -> f32 {
+    i16_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_fixed_to_f32(x: u32, frac: u32) -> f32 {
+    u32_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_fixed_to_f32(x: i32, frac: u32) -> f32 {
+    i32_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_fixed_to_f32(x: u64, frac: u32) -> f32 {
+    u64_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_fixed_to_f32(x: i64, frac: u32) -> f32 {
+    i64_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_fixed_to_f32(x: u128, frac: u32) -> f32 {
+    u128_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_fixed_to_f32(x: i128, frac: u32) -> f32 {
+    i128_to_f32_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_fixed_to_f64(x: u16, frac: u32) -> f64 {
+    u16_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_fixed_to_f64(x: i16, frac: u32) -> f64 {
+    i16_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_fixed_to_f64(x: u32, frac: u32) -> f64 {
+    u32_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_fixed_to_f64(x: i32, frac: u32) -> f64 {
+    i32_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_fixed_to_f64(x: u64, frac: u32) -> f64 {
+    u64_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_fixed_to_f64(x: i64, frac: u32) -> f64 {
+    i64_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_fixed_to_f64(x: u128, frac: u32) -> f64 {
+    u128_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_fixed_to_f64(x: i128, frac: u32) -> f64 {
+    i128_to_f64_scaled(x, -crate::soft::fixed_exponent(frac))
+}
+
+macro_rules! impl_float_to_fixed {
+    ($name:ident $float:tt $int:tt $bits:tt $bias:literal) => {
+        #[cfg_attr(not(noinline), inline)]
+        pub fn $name(f: $float, frac: u32, round: Round) -> $int {
+            if frac > $bias {
+                return crate::soft::$name(f.to_bits(), frac, round);
+            }
+            let y = f * $float::from_bits(($bias + frac as $bits) << ($float::MANTISSA_DIGITS - 1));
+            let t = y as $int; // Truncated and saturated.
+            let d = y - t as $float; // Exact, unless saturated, in which case rounding makes no difference.
+            let half = if d.abs() > 0.5 {
+                Ordering::Greater
+            } else if d.abs() == 0.5 {
+                Ordering::Equal
+            } else {
+                Ordering::Less // Including NaN.
+            };
+            if round.round_up(d < 0.0, t & 1 != 0, half, d != 0.0 && !d.is_nan()) {
+                if d < 0.0 { t.saturating_sub(1) } else { t.saturating_add(1) }
+            } else {
+                t
+            }
+        }
+    };
+}
+
+impl_float_to_fixed!(f32_to_u16_fixed f32 u16 u32 127);
+impl_float_to_fixed!(f32_to_i16_fixed f32 i16 u32 127);
+impl_float_to_fixed!(f32_to_u32_fixed f32 u32 u32 127);
+impl_float_to_fixed!(f32_to_i32_fixed f32 i32 u32 127);
+impl_float_to_fixed!(f32_to_u64_fixed f32 u64 u32 127);
+impl_float_to_fixed!(f32_to_i64_fixed f32 i64 u32 127);
+impl_float_to_fixed!(f32_to_u128_fixed f32 u128 u32 127);
+impl_float_to_fixed!(f32_to_i128_fixed f32 i128 u32 127);
+impl_float_to_fixed!(f64_to_u16_fixed f64 u16 u64 1023);
+impl_float_to_fixed!(f64_to_i16_fixed f64 i16 u64 1023);
+impl_float_to_fixed!(f64_to_u32_fixed f64 u32 u64 1023);
+impl_float_to_fixed!(f64_to_i32_fixed f64 i32 u64 1023);
+impl_float_to_fixed!(f64_to_u64_fixed f64 u64 u64 1023);
+impl_float_to_fixed!(f64_to_i64_fixed f64 i64 u64 1023);
+impl_float_to_fixed!(f64_to_u128_fixed f64 u128 u64 1023);
+impl_float_to_fixed!(f64_to_i128_fixed f64 i128 u64 1023);
@@ -0,0 +1,308 @@
+//! Conversion functions operating on slices.
+//!
+//! Each function converts all elements of `src` and stores the results in
+//! `dst`, which must have the same length as `src`. The results are identical
+//! to those of the corresponding scalar functions in [`fast`](crate::fast).
+//!
+//! Conversions that are not available as vector instructions are vectorized
+//! by hand where possible: using SSE2 or AVX2 on x86, and NEON on AArch64.
+//! 32-bit ARM always uses the scalar functions, because its `neon` target
+//! feature cannot be detected at compile time on stable Rust.
+//! All other conversions, and the remainder of a slice that doesn't fill a
+//! whole vector, use the scalar functions.
+//!
+//! # Panics
+//!
+//! All functions in this module panic if `src` and `dst` differ in length.
+
+macro_rules! impl_slice {
+    ($name:tt $from:tt $to:tt) => {
+        #[inline]
+        pub fn $name(src: &[$from], dst: &mut [$to]) {
+            assert_eq!(src.len(), dst.len(), "slices must have the same length");
+            let n = simd::$name(src, dst);
+            for (d, &s) in dst[n..].iter_mut().zip(&src[n..]) {
+                *d = super::$name(s);
+            }
+        }
+    };
+}
+
+impl_slice!(f32_to_u32 f32 u32);
+impl_slice!(f32_to_i32 f32 i32);
+impl_slice!(f32_to_u64 f32 u64);
+impl_slice!(f32_to_i64 f32 i64);
+impl_slice!(f32_to_u128 f32 u128);
+impl_slice!(f32_to_i128 f32 i128);
+impl_slice!(f64_to_u32 f64 u32);
+impl_slice!(f64_to_i32 f64 i32);
+impl_slice!(f64_to_u64 f64 u64);
+impl_slice!(f64_to_i64 f64 i64);
+impl_slice!(f64_to_u128 f64 u128);
+impl_slice!(f64_to_i128 f64 i128);
+impl_slice!(u8_to_f32 u8 f32);
+impl_slice!(i8_to_f32 i8 f32);
+impl_slice!(u16_to_f32 u16 f32);
+impl_slice!(i16_to_f32 i16 f32);
+impl_slice!(u32_to_f32 u32 f32);
+impl_slice!(i32_to_f32 i32 f32);
+impl_slice!(u64_to_f32 u64 f32);
+impl_slice!(i64_to_f32 i64 f32);
+impl_slice!(u128_to_f32 u128 f32);
+impl_slice!(i128_to_f32 i128 f32);
+impl_slice!(u8_to_f64 u8 f64);
+impl_slice!(i8_to_f64 i8 f64);
+impl_slice!(u16_to_f64 u16 f64);
+impl_slice!(i16_to_f64 i16 f64);
+impl_slice!(u32_to_f64 u32 f64);
+impl_slice!(i32_to_f64 i32 f64);
+impl_slice!(u64_to_f64 u64 f64);
+impl_slice!(i64_to_f64 i64 f64);
+impl_slice!(u128_to_f64 u128 f64);
+impl_slice!(i128_to_f64 i128 f64);
+
+/// Vectorized implementations.
+///
+/// Every function converts a prefix of `src` into `dst` and returns the length
+/// of that prefix. The rest is left to the scalar implementation.
+mod simd {
+    #[allow(unused_macros)]
+    macro_rules! impl_none {
+        ($($name:tt $from:tt $to:tt;)*) => {
+            $(
+                #[inline]
+                pub fn $name(_: &[$from], _: &mut [$to]) -> usize {
+                    0
+                }
+            )*
+        };
+    }
+
+    // These are either available natively as vector instructions, or can't be
+    // vectorized easily. Either way, the scalar loop is good enough.
+    impl_none! {
+        f32_to_u32 f32 u32;
+        f32_to_u64 f32 u64;
+        f32_to_i64 f32 i64;
+        f32_to_u128 f32 u128;
+        f32_to_i128 f32 i128;
+        f64_to_u32 f64 u32;
+        f64_to_i32 f64 i32;
+        f64_to_u64 f64 u64;
+        f64_to_i64 f64 i64;
+        f64_to_u128 f64 u128;
+        f64_to_i128 f64 i128;
+        u8_to_f32 u8 f32;
+        i8_to_f32 i8 f32;
+        u16_to_f32 u16 f32;
+        i16_to_f32 i16 f32;
+        i32_to_f32 i32 f32;
+        u64_to_f32 u64 f32;
+        i64_to_f32 i64 f32;
+        u128_to_f32 u128 f32;
+        i128_to_f32 i128 f32;
+        u8_to_f64 u8 f64;
+        i8_to_f64 i8 f64;
+        u16_to_f64 u16 f64;
+        i16_to_f64 i16 f64;
+        u32_to_f64 u32 f64;
+        i32_to_f64 i32 f64;
+        u128_to_f64 u128 f64;
+        i128_to_f64 i128 f64;
+    }
+
+    #[cfg(not(any(
+        target_arch = "aarch64",
+        target_arch = "x86_64",
+        all(target_arch = "x86", target_feature = "sse2"),
+    )))]
+    impl_none! {
+        f32_to_i32 f32 i32;
+        u32_to_f32 u32 f32;
+        u64_to_f64 u64 f64;
+        i64_to_f64 i64 f64;
+    }
+
+    #[cfg(any(
+        target_arch = "x86_64",
+        all(target_arch = "x86", target_feature = "sse2"),
+    ))]
+    group! {
+        #[cfg(target_arch = "x86")]
+        use core::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use core::arch::x86_64::*;
+
+        const A: f64 = (1u128 << 52) as f64;
+        const B: f64 = (1u128 << 84) as f64;
+        const C: f64 = ((1u128 << 84) + (1u128 << 63)) as f64;
+
+        // Same as special::u64_to_f64, on two lanes at once.
+        #[cfg(not(target_feature = "avx2"))]
+        #[inline]
+        pub fn u64_to_f64(src: &[u64], dst: &mut [f64]) -> usize {
+            for (s, d) in src.chunks_exact(2).zip(dst.chunks_exact_mut(2)) {
+                unsafe {
+                    let x = _mm_loadu_si128(s.as_ptr() as *const __m128i);
+                    let l = _mm_or_si128(_mm_and_si128(x, _mm_set1_epi64x(0xFFFF_FFFF)), _mm_set1_epi64x(A.to_bits() as i64));
+                    let h = _mm_or_si128(_mm_srli_epi64(x, 32), _mm_set1_epi64x(B.to_bits() as i64));
+                    let l = _mm_sub_pd(_mm_castsi128_pd(l), _mm_set1_pd(A));
+                    let h = _mm_sub_pd(_mm_castsi128_pd(h), _mm_set1_pd(B));
+                    _mm_storeu_pd(d.as_mut_ptr(), _mm_add_pd(l, h));
+                }
+            }
+            src.len() & !1
+        }
+
+        // Same as u64_to_f64, but with the high half offset by 2^31 to make it unsigned.
+        #[cfg(not(target_feature = "avx2"))]
+        #[inline]
+        pub fn i64_to_f64(src: &[i64], dst: &mut [f64]) -> usize {
+            for (s, d) in src.chunks_exact(2).zip(dst.chunks_exact_mut(2)) {
+                unsafe {
+                    let x = _mm_loadu_si128(s.as_ptr() as *const __m128i);
+                    let l = _mm_or_si128(_mm_and_si128(x, _mm_set1_epi64x(0xFFFF_FFFF)), _mm_set1_epi64x(A.to_bits() as i64));
+                    let h = _mm_xor_si128(_mm_srli_epi64(x, 32), _mm_set1_epi64x(B.to_bits() as i64 | 1 << 31));
+                    let l = _mm_sub_pd(_mm_castsi128_pd(l), _mm_set1_pd(A));
+                    let h = _mm_sub_pd(_mm_castsi128_pd(h), _mm_set1_pd(C));
+                    _mm_storeu_pd(d.as_mut_ptr(), _mm_add_pd(l, h));
+                }
+            }
+            src.len() & !1
+        }
+
+        // Both 16-bit halves are converted exactly, so the addition is the only rounding step.
+        #[cfg(not(target_feature = "avx2"))]
+        #[inline]
+        pub fn u32_to_f32(src: &[u32], dst: &mut [f32]) -> usize {
+            for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+                unsafe {
+                    let x = _mm_loadu_si128(s.as_ptr() as *const __m128i);
+                    let l = _mm_cvtepi32_ps(_mm_and_si128(x, _mm_set1_epi32(0xFFFF)));
+                    let h = _mm_cvtepi32_ps(_mm_srli_epi32(x, 16));
+                    let h = _mm_mul_ps(h, _mm_set1_ps(65536.0));
+                    _mm_storeu_ps(d.as_mut_ptr(), _mm_add_ps(l, h));
+                }
+            }
+            src.len() & !3
+        }
+
+        // cvttps2dq results in i32::MIN for all out of range values, including NaN.
+        #[cfg(not(target_feature = "avx2"))]
+        #[inline]
+        pub fn f32_to_i32(src: &[f32], dst: &mut [i32]) -> usize {
+            for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+                unsafe {
+                    let x = _mm_loadu_ps(s.as_ptr());
+                    let i = _mm_cvttps_epi32(x);
+                    let max = _mm_castps_si128(_mm_cmpge_ps(x, _mm_set1_ps(2147483648.0)));
+                    let not_nan = _mm_castps_si128(_mm_cmpord_ps(x, x));
+                    let i = _mm_and_si128(_mm_xor_si128(i, max), not_nan);
+                    _mm_storeu_si128(d.as_mut_ptr() as *mut __m128i, i);
+                }
+            }
+            src.len() & !3
+        }
+
+        #[cfg(target_feature = "avx2")]
+        #[inline]
+        pub fn u64_to_f64(src: &[u64], dst: &mut [f64]) -> usize {
+            for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+                unsafe {
+                    let x = _mm256_loadu_si256(s.as_ptr() as *const __m256i);
+                    let l = _mm256_or_si256(_mm256_and_si256(x, _mm256_set1_epi64x(0xFFFF_FFFF)), _mm256_set1_epi64x(A.to_bits() as i64));
+                    let h = _mm256_or_si256(_mm256_srli_epi64(x, 32), _mm256_set1_epi64x(B.to_bits() as i64));
+                    let l = _mm256_sub_pd(_mm256_castsi256_pd(l), _mm256_set1_pd(A));
+                    let h = _mm256_sub_pd(_mm256_castsi256_pd(h), _mm256_set1_pd(B));
+                    _mm256_storeu_pd(d.as_mut_ptr(), _mm256_add_pd(l, h));
+                }
+            }
+            src.len() & !3
+        }
+
+        #[cfg(target_feature = "avx2")]
+        #[inline]
+        pub fn i64_to_f64(src: &[i64], dst: &mut [f64]) -> usize {
+            for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+                unsafe {
+                    let x = _mm256_loadu_si256(s.as_ptr() as *const __m256i);
+                    let l = _mm256_or_si256(_mm256_and_si256(x, _mm256_set1_epi64x(0xFFFF_FFFF)), _mm256_set1_epi64x(A.to_bits() as i64));
+                    let h = _mm256_xor_si256(_mm256_srli_epi64(x, 32), _mm256_set1_epi64x(B.to_bits() as i64 | 1 << 31));
+                    let l = _mm256_sub_pd(_mm256_castsi256_pd(l), _mm256_set1_pd(A));
+                    let h = _mm256_sub_pd(_mm256_castsi256_pd(h), _mm256_set1_pd(C));
+                    _mm256_storeu_pd(d.as_mut_ptr(), _mm256_add_pd(l, h));
+                }
+            }
+            src.len() & !3
+        }
+
+        #[cfg(target_feature = "avx2")]
+        #[inline]
+        pub fn u32_to_f32(src: &[u32], dst: &mut [f32]) -> usize {
+            for (s, d) in src.chunks_exact(8).zip(dst.chunks_exact_mut(8)) {
+                unsafe {
+                    let x = _mm256_loadu_si256(s.as_ptr() as *const __m256i);
+                    let l = _mm256_cvtepi32_ps(_mm256_and_si256(x, _mm256_set1_epi32(0xFFFF)));
+                    let h = _mm256_cvtepi32_ps(_mm256_srli_epi32(x, 16));
+                    let h = _mm256_mul_ps(h, _mm256_set1_ps(65536.0));
+                    _mm256_storeu_ps(d.as_mut_ptr(), _mm256_add_ps(l, h));
+                }
+            }
+            src.len() & !7
+        }
+
+        #[cfg(target_feature = "avx2")]
+        #[inline]
+        pub fn f32_to_i32(src: &[f32], dst: &mut [i32]) -> usize {
+            for (s, d) in src.chunks_exact(8).zip(dst.chunks_exact_mut(8)) {
+                unsafe {
+                    let x = _mm256_loadu_ps(s.as_ptr());
+                    let i = _mm256_cvttps_epi32(x);
+                    let max = _mm256_castps_si256(_mm256_cmp_ps::<_CMP_GE_OQ>(x, _mm256_set1_ps(2147483648.0)));
+                    let not_nan = _mm256_castps_si256(_mm256_cmp_ps::<_CMP_ORD_Q>(x, x));
+                    let i = _mm256_and_si256(_mm256_xor_si256(i, max), not_nan);
+                    _mm256_storeu_si256(d.as_mut_ptr() as *mut __m256i, i);
+                }
+            }
+            src.len() & !7
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    group! {
+        use core::arch::aarch64::*;
+
+        #[inline]
+        pub fn u64_to_f64(src: &[u64], dst: &mut [f64]) -> usize {
+            for (s, d) in src.chunks_exact(2).zip(dst.chunks_exact_mut(2)) {
+                unsafe { vst1q_f64(d.as_mut_ptr(), vcvtq_f64_u64(vld1q_u64(s.as_ptr()))) }
+            }
+            src.len() & !1
+        }
+
+        #[inline]
+        pub fn i64_to_f64(src: &[i64], dst: &mut [f64]) -> usize {
+            for (s, d) in src.chunks_exact(2).zip(dst.chunks_exact_mut(2)) {
+                unsafe { vst1q_f64(d.as_mut_ptr(), vcvtq_f64_s64(vld1q_s64(s.as_ptr()))) }
+            }
+            src.len() & !1
+        }
+
+        #[inline]
+        pub fn u32_to_f32(src: &[u32], dst: &mut [f32]) -> usize {
+            for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+                unsafe { vst1q_f32(d.as_mut_ptr(), vcvtq_f32_u32(vld1q_u32(s.as_ptr()))) }
+            }
+            src.len() & !3
+        }
+
+        // fcvtzs saturates and converts NaN to zero, just like `as`.
+        #[inline]
+        pub fn f32_to_i32(src: &[f32], dst: &mut [i32]) -> usize {
+            for (s, d) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+                unsafe { vst1q_s32(d.as_mut_ptr(), vcvtq_s32_f32(vld1q_f32(s.as_ptr()))) }
+            }
+            src.len() & !3
+        }
+    }
+}
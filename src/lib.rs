@@ -1,4 +1,5 @@
 #![cfg_attr(not(test), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 //! Floating point conversion functions.
 //!
@@ -13,6 +14,24 @@
 //! functions by making use of native floating point instructions where
 //! possible.
 //!
+//! The [`fast::slice`] module converts whole slices at once, using SIMD
+//! instructions where possible.
+//!
+//! The [`minifloat`] module provides generic conversion functions for any
+//! floating point format, described by its number of exponent and mantissa
+//! bits, its exponent bias, and whether it has infinities, NaN and subnormals.
+//!
+//! The [`mx`] module converts blocks of `f32` values to and from the OCP
+//! Microscaling (MX) formats, using the software implementations.
+//!
+//! The [`decimal`] module converts to and from the IEEE 754 decimal floating
+//! point formats. Its conversions take the rounding mode as a [`Round`]
+//! argument.
+//!
+//! The [`simd`] module, available with the `simd` feature on nightly, provides
+//! versions of the software implementations that operate on all lanes of a
+//! portable SIMD vector at once.
+//!
 //! ## Conversion of integers to floating point values
 //!
 //! - Functions named `_round` round the integer to the closest possible
@@ -21,8 +40,21 @@
 //!   towards zero.
 //! - Functions without a rounding mode in their name do not round. These
 //!   conversions are always lossless.
-//! - The only conversion that can overflow is `u128_to_f32_round`, in which
-//!   case it returns `f32::INFINITY`.
+//! - The only conversions that can overflow are `u128_to_f32_round`,
+//!   `u128_to_tf32`, `u128_to_bf16`, the conversions to `f16` and `f8e5m2`
+//!   from `u16` and integers of 32 bits or more, and the conversions to
+//!   `f8e4m3` from integers of 16 bits or more, in which case they return
+//!   infinity (or NaN for `f8e4m3`, which has no infinity). Conversions to
+//!   the 8-bit formats also have a `_saturate` variant, which results in the
+//!   largest finite value instead.
+//! - Functions named `_scaled` multiply the integer by 2<sup>`exp`</sup> and
+//!   round once, to the closest possible floating point number (breaking ties
+//!   to even). The result can be subnormal, zero or infinity.
+//! - Functions named `ratio_` divide one integer by another, and round the
+//!   quotient once, according to their [`Round`] argument. Division by zero
+//!   results in infinity, or NaN for zero divided by zero. On overflow, they
+//!   result in infinity or the largest finite value, depending on the rounding
+//!   mode.
 //!
 //! ## Conversion of floating point values to integers
 //!
@@ -33,6 +65,30 @@
 //!   in the minimum integer value.
 //! - `NaN` is converted to zero.
 //!
+//! ## Conversion between floating point formats
+//!
+//! - Conversions to a wider format are always lossless.
+//! - Conversions to a narrower format round to the closest possible floating
+//!   point number, break ties to even, and overflow to infinity.
+//! - Functions named `_truncate`, `_ceil` and `_floor` instead round towards
+//!   zero, towards positive infinity, and towards negative infinity. When
+//!   rounding towards zero on overflow, they result in the largest finite
+//!   value rather than infinity.
+//! - `NaN` stays `NaN`, but becomes quiet. The payload is kept as far as it
+//!   fits.
+//! - Conversions to the 8-bit formats `f8e4m3` and `f8e5m2` also have a
+//!   `_saturate` variant, which results in the largest finite value instead
+//!   of overflowing, including for infinity.
+//!
+//! ## Conversion of fixed point numbers
+//!
+//! - Functions named `_fixed` convert to or from integers with a number of
+//!   fractional bits given by their `frac` argument, such as Q15 or Q16.16.
+//! - Conversions to floating point round to the closest possible floating
+//!   point number, and break ties to even.
+//! - Conversions to fixed point round according to their [`Round`] argument,
+//!   and otherwise behave like the conversions to integers.
+//!
 //! ## Speed
 //!
 //! For conversions that aren't available natively, the software
@@ -45,7 +101,7 @@
 //! This crate is usable, but still incomplete:
 //!
 //! - Native conversions are only available on ARM (32- and 64-bit) and x86 (32- and 64-bit).
-//! - The truncating functions do not (yet) use any native floating point instructions.
+//! - The truncating conversions from integers to floating point values do not (yet) use any native floating point instructions.
 
 // Used to group items together for #[cfg(..)].
 macro_rules! group {
@@ -59,4 +115,58 @@ pub mod soft;
 
 pub mod fast;
 
+pub mod minifloat;
+
+pub mod mx;
+
+pub mod decimal;
+
+#[cfg(feature = "simd")]
+pub mod simd;
+
 mod special;
+
+use core::cmp::Ordering;
+
+/// A rounding mode, as defined by IEEE 754.
+///
+/// Only used by functions that take the rounding mode as an argument. All
+/// other functions have the rounding mode in their name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Round {
+    /// Round to the closest value, and break ties to even.
+    TiesToEven,
+    /// Round to the closest value, and break ties away from zero.
+    TiesToAway,
+    /// Round towards zero (truncate).
+    TowardZero,
+    /// Round towards positive infinity (ceil).
+    TowardPositive,
+    /// Round towards negative infinity (floor).
+    TowardNegative,
+}
+
+impl Round {
+    /// Whether a truncated magnitude needs to be incremented, given how the
+    /// discarded part compares to half a unit, and whether it is non-zero.
+    fn round_up(self, negative: bool, odd: bool, half: Ordering, inexact: bool) -> bool {
+        match self {
+            Round::TiesToEven => half == Ordering::Greater || half == Ordering::Equal && odd,
+            Round::TiesToAway => half != Ordering::Less,
+            Round::TowardZero => false,
+            Round::TowardPositive => inexact && !negative,
+            Round::TowardNegative => inexact && negative,
+        }
+    }
+
+    /// Whether a value too large to represent results in infinity, rather
+    /// than the largest finite value.
+    fn overflow_to_infinity(self, negative: bool) -> bool {
+        match self {
+            Round::TiesToEven | Round::TiesToAway => true,
+            Round::TowardZero => false,
+            Round::TowardPositive => !negative,
+            Round::TowardNegative => negative,
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Conversion functions for custom floating point formats.
+//!
+//! A format is described by a type implementing [`Format`], which specifies
+//! the number of exponent and mantissa bits, the exponent bias, and whether
+//! the format has infinities, NaN and subnormal numbers. A number of common
+//! formats are provided, but any other format of at most 64 bits can be
+//! described as well.
+//!
+//! Values are represented by their bits in a `u64`: the mantissa in the
+//! lowest bits, followed by the exponent and then the sign bit. All other bits
+//! must be zero.
+//!
+//! All conversions follow the same rules as the rest of this crate:
+//!
+//! - Conversions round to the closest possible value, and break ties to even.
+//! - Overflow results in infinity. For formats without infinity, it results
+//!   in NaN, or in the largest finite value if the format doesn't have NaN
+//!   either.
+//! - `NaN` stays `NaN`, but becomes quiet. The payload is kept as far as it
+//!   fits. For formats without NaN, it results in zero.
+//! - Conversions to integers truncate and saturate, and convert `NaN` to zero.
+//! - For formats without subnormal numbers, an exponent of zero represents
+//!   zero, and values that would round to a subnormal number result in zero.
+//!   If such a format has no infinity or NaN either, zero is always positive.
+//!
+//! These are generic implementations that work for any format. The functions
+//! in [`soft`](crate::soft) are faster for the formats they support.
+
+/// Description of a floating point format.
+///
+/// For example, the OCP FP6 E3M2 format, which has no infinity or NaN, is
+/// described as follows:
+///
+/// ```
+/// struct E3M2;
+///
+/// impl floatconv::minifloat::Format for E3M2 {
+///     const EXPONENT_BITS: u32 = 3;
+///     const MANTISSA_BITS: u32 = 2;
+///     const INFINITY: bool = false;
+///     const NAN: bool = false;
+/// }
+///
+/// assert_eq!(floatconv::minifloat::from_u32::<E3M2>(28), 0b0_111_11);
+/// ```
+pub trait Format {
+    /// The number of bits of the exponent, between 1 and 15.
+    const EXPONENT_BITS: u32;
+
+    /// The number of bits of the mantissa, not counting the implicit 1-bit.
+    ///
+    /// Together with the exponent and sign bits, there can be at most 64 bits.
+    const MANTISSA_BITS: u32;
+
+    /// The exponent bias. By default, 2<sup>`EXPONENT_BITS` − 1</sup> − 1.
+    const BIAS: i32 = (1 << (Self::EXPONENT_BITS - 1)) - 1;
+
+    /// Whether the largest exponent is reserved for infinity and NaN, like in
+    /// IEEE 754. This requires at least one mantissa bit.
+    const INFINITY: bool = true;
+
+    /// Only for formats without infinity: whether the encoding with all bits
+    /// set (except the sign bit) represents NaN, like in OCP FP8 E4M3.
+    const NAN: bool = true;
+
+    /// Whether an exponent of zero represents subnormal numbers.
+    const SUBNORMALS: bool = true;
+}
+
+/// Defines a format: its exponent bits, mantissa bits, (optionally) bias,
+/// and the flags that differ from the defaults.
+macro_rules! format {
+    ($(#[$attr:meta])* $name:ident $e:literal $m:literal $(($bias:literal))? $($key:ident = $value:literal)*) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name;
+
+        impl Format for $name {
+            const EXPONENT_BITS: u32 = $e;
+            const MANTISSA_BITS: u32 = $m;
+            $(const BIAS: i32 = $bias;)?
+            $(const $key: bool = $value;)*
+        }
+    };
+}
+
+format!(/** IEEE 754 double precision (`f64`). */ F64 11 52);
+format!(/** IEEE 754 single precision (`f32`). */ F32 8 23);
+format!(/** IEEE 754 half precision (`f16`). */ F16 5 10);
+format!(/** bfloat16, the upper half of `f32`. */ Bf16 8 7);
+format!(/** NVIDIA TensorFloat-32: the 19 upper bits of `f32`. */ Tf32 8 10);
+format!(/** 24-bit floating point, as used by older AMD GPUs. */ Fp24 7 16);
+format!(/** OCP FP8 E4M3, without infinity. */ F8E4M3 4 3 INFINITY = false);
+format!(/** OCP FP8 E5M2. */ F8E5M2 5 2);
+format!(/** OCP FP6 E3M2, without infinity or NaN. */ F6E3M2 3 2 INFINITY = false NAN = false);
+format!(/** OCP FP6 E2M3, without infinity or NaN. */ F6E2M3 2 3 INFINITY = false NAN = false);
+format!(/** OCP FP4 E2M1, without infinity or NaN. */ F4E2M1 2 1 INFINITY = false NAN = false);
+format!(
+    /// VAX F_floating, in its logical bit order (not word-swapped).
+    ///
+    /// Reserved operands (negative zero encodings) are treated as zero.
+    VaxF 8 23 (129) INFINITY = false NAN = false SUBNORMALS = false
+);
+format!(
+    /// VAX D_floating, in its logical bit order (not word-swapped).
+    ///
+    /// Reserved operands (negative zero encodings) are treated as zero.
+    VaxD 8 55 (129) INFINITY = false NAN = false SUBNORMALS = false
+);
+format!(
+    /// VAX G_floating, in its logical bit order (not word-swapped).
+    ///
+    /// Reserved operands (negative zero encodings) are treated as zero.
+    VaxG 11 52 (1025) INFINITY = false NAN = false SUBNORMALS = false
+);
+
+/// A decoded value, without its sign.
+enum Value {
+    Zero,
+    /// m × 2<sup>e</sup>, with m > 0.
+    Finite(u128, i32),
+    Infinity,
+    /// The payload, excluding the quiet bit, aligned to the top of the `u64`.
+    NaN(u64),
+}
+
+fn sign_bit<F: Format>() -> u64 {
+    1 << (F::EXPONENT_BITS + F::MANTISSA_BITS)
+}
+
+/// The largest value of the exponent field.
+fn max_exponent<F: Format>() -> u64 {
+    (1 << F::EXPONENT_BITS) - 1
+}
+
+/// The bits of the largest finite value.
+fn max<F: Format>() -> u64 {
+    if F::INFINITY {
+        (max_exponent::<F>() << F::MANTISSA_BITS) - 1
+    } else if F::NAN {
+        sign_bit::<F>() - 2
+    } else {
+        sign_bit::<F>() - 1
+    }
+}
+
+/// The bits of the result on overflow.
+fn overflow<F: Format>() -> u64 {
+    if F::INFINITY {
+        max_exponent::<F>() << F::MANTISSA_BITS
+    } else {
+        max::<F>() + F::NAN as u64 // All bits set if that's NaN.
+    }
+}
+
+fn decode<F: Format>(x: u64) -> Value {
+    let a = x & (sign_bit::<F>() - 1); // Remove sign bit.
+    let e = a >> F::MANTISSA_BITS;
+    let m = a & !(!0 << F::MANTISSA_BITS);
+    if F::INFINITY && e == max_exponent::<F>() {
+        if m == 0 {
+            Value::Infinity
+        } else {
+            Value::NaN(m << (64 - F::MANTISSA_BITS) << 1)
+        }
+    } else if !F::INFINITY && F::NAN && a == sign_bit::<F>() - 1 {
+        Value::NaN(0)
+    } else if e == 0 {
+        if m == 0 || !F::SUBNORMALS {
+            Value::Zero
+        } else {
+            Value::Finite(m.into(), 1 - F::BIAS - F::MANTISSA_BITS as i32)
+        }
+    } else {
+        Value::Finite((m | 1 << F::MANTISSA_BITS).into(), e as i32 - F::BIAS - F::MANTISSA_BITS as i32)
+    }
+}
+
+fn encode<F: Format>(negative: bool, v: Value) -> u64 {
+    let a = match v {
+        Value::Zero => 0,
+        Value::Finite(m, e) => round::<F>(m, e),
+        Value::Infinity => overflow::<F>(),
+        Value::NaN(payload) if F::INFINITY => {
+            let payload = payload.checked_shr(65 - F::MANTISSA_BITS).unwrap_or(0); // Keep the upper bits of the payload.
+            overflow::<F>() | 1 << (F::MANTISSA_BITS - 1) | payload // But make it quiet.
+        }
+        Value::NaN(_) if F::NAN => sign_bit::<F>() - 1,
+        Value::NaN(_) => return 0,
+    };
+    if negative && (a != 0 || F::SUBNORMALS || F::INFINITY || F::NAN) {
+        a | sign_bit::<F>()
+    } else {
+        a // Without any special values, a negative zero would be a reserved operand, as in VAX.
+    }
+}
+
+/// Rounds m × 2<sup>e</sup> (with m > 0) to the closest value of the format,
+/// and returns its bits.
+fn round<F: Format>(m: u128, e: i32) -> u64 {
+    let n = 127 - m.leading_zeros() as i32; // Position of the highest 1-bit.
+    let exp = n + e; // The value is >= 2^exp, < 2^(exp + 1).
+    let emin = 1 - F::BIAS; // Exponent of the smallest normal number.
+    if exp - emin >= 1 << F::EXPONENT_BITS { // Far too large, even when ignoring the top exponent.
+        return overflow::<F>();
+    }
+    let s = exp.max(emin) - F::MANTISSA_BITS as i32 - e; // Number of insignificant bits.
+    let r = if s <= 0 {
+        (m << -s) as u64 // Exact.
+    } else if s > n + 1 {
+        0 // Less than half of the smallest step.
+    } else {
+        let x = m >> s; // Significant bits.
+        let y = m << (128 - s); // Insignificant bits, only relevant for rounding.
+        (x + ((y - (y >> 127 & !x)) >> 127)) as u64 // Add one when we need to round up. Break ties to even.
+    };
+    let a = (((exp - emin).max(0) as u64) << F::MANTISSA_BITS) + r; // + not |, so the mantissa can overflow into the exponent.
+    if a > max::<F>() {
+        overflow::<F>()
+    } else if a >> F::MANTISSA_BITS == 0 && !F::SUBNORMALS {
+        0
+    } else {
+        a
+    }
+}
+
+/// Converts from one format to another.
+pub fn convert<A: Format, B: Format>(x: u64) -> u64 {
+    encode::<B>(x & sign_bit::<A>() != 0, decode::<A>(x))
+}
+
+/// Converts the bits of an `f32` to the given format.
+pub fn from_f32<F: Format>(f: u32) -> u64 {
+    convert::<F32, F>(f.into())
+}
+
+/// Converts the bits of an `f64` to the given format.
+pub fn from_f64<F: Format>(f: u64) -> u64 {
+    convert::<F64, F>(f)
+}
+
+/// Converts from the given format to the bits of an `f32`.
+pub fn to_f32<F: Format>(x: u64) -> u32 {
+    convert::<F, F32>(x) as u32
+}
+
+/// Converts from the given format to the bits of an `f64`.
+pub fn to_f64<F: Format>(x: u64) -> u64 {
+    convert::<F, F64>(x)
+}
+
+/// Truncates a value to an integer, and returns its magnitude, saturated to
+/// `u128::MAX`, or `None` for NaN.
+fn truncate<F: Format>(x: u64) -> Option<u128> {
+    match decode::<F>(x) {
+        Value::Zero => Some(0),
+        Value::Finite(m, e) if e < 0 => Some(m.checked_shr(-e as u32).unwrap_or(0)),
+        Value::Finite(m, e) if e as u32 <= m.leading_zeros() => Some(m << e),
+        Value::Finite(..) | Value::Infinity => Some(u128::MAX),
+        Value::NaN(_) => None,
+    }
+}
+
+macro_rules! impl_uint {
+    ($from:ident $to:ident $t:ty) => {
+        #[doc = concat!("Converts a `", stringify!($t), "` to the given format.")]
+        pub fn $from<F: Format>(x: $t) -> u64 {
+            encode::<F>(false, if x == 0 { Value::Zero } else { Value::Finite(x.into(), 0) })
+        }
+
+        #[doc = concat!("Converts from the given format to a `", stringify!($t), "`.")]
+        pub fn $to<F: Format>(x: u64) -> $t {
+            match truncate::<F>(x) {
+                Some(a) if x & sign_bit::<F>() == 0 => a.min(<$t>::MAX.into()) as $t,
+                _ => 0, // Negative or NaN
+            }
+        }
+    };
+}
+
+macro_rules! impl_int {
+    ($from:ident $to:ident $t:ty) => {
+        #[doc = concat!("Converts an `", stringify!($t), "` to the given format.")]
+        pub fn $from<F: Format>(i: $t) -> u64 {
+            encode::<F>(i < 0, if i == 0 { Value::Zero } else { Value::Finite(i.unsigned_abs().into(), 0) })
+        }
+
+        #[doc = concat!("Converts from the given format to an `", stringify!($t), "`.")]
+        pub fn $to<F: Format>(x: u64) -> $t {
+            match truncate::<F>(x) {
+                Some(a) if x & sign_bit::<F>() == 0 => a.min(<$t>::MAX as u128) as $t,
+                Some(a) => (a.min(<$t>::MIN.unsigned_abs().into()) as $t).wrapping_neg(),
+                None => 0,
+            }
+        }
+    };
+}
+
+impl_uint!(from_u8 to_u8 u8);
+impl_uint!(from_u16 to_u16 u16);
+impl_uint!(from_u32 to_u32 u32);
+impl_uint!(from_u64 to_u64 u64);
+impl_uint!(from_u128 to_u128 u128);
+impl_int!(from_i8 to_i8 i8);
+impl_int!(from_i16 to_i16 i16);
+impl_int!(from_i32 to_i32 i32);
+impl_int!(from_i64 to_i64 i64);
+impl_int!(from_i128 to_i128 i128);
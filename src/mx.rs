@@ -0,0 +1,140 @@
+//! Conversion to and from the OCP Microscaling (MX) block formats.
+//!
+//! An MX block consists of [`BLOCK_SIZE`] elements that share a single scale.
+//! The scale is an E8M0 value `s`, representing 2<sup>s − 127</sup>, or NaN
+//! if `s` is 255. Each element is an FP8 (E4M3 or E5M2), FP6 (E3M2 or E2M3),
+//! FP4 (E2M1) or INT8 value, which is multiplied by the scale.
+//!
+//! Every element is stored in its own byte. FP6 and FP4 elements use the
+//! lower six or four bits, with the sign bit in bit 5 or bit 3. INT8
+//! elements are two's complement, with an implicit scale of 2<sup>−6</sup>.
+//!
+//! Conversion to an MX format follows the OCP Microscaling Formats (MX)
+//! Specification v1.0:
+//!
+//! - The scale is the largest power of two not above the largest magnitude
+//!   in the block, divided by the largest power of two of the element format.
+//!   It is never lower than 2<sup>−127</sup>.
+//! - Each element is rounded to the closest value, breaking ties to even,
+//!   and values above the largest element value are clamped to it.
+//! - A block containing NaN or infinity results in a NaN scale, and zero for
+//!   all elements.
+//!
+//! Conversion from an MX format rounds to the closest `f32` (breaking ties to
+//! even), which only makes a difference for results outside the normal range
+//! of `f32`. A NaN scale results in NaN for all elements.
+
+use crate::minifloat;
+use crate::soft;
+use crate::Round;
+
+/// The number of elements in an MX block.
+pub const BLOCK_SIZE: usize = 32;
+
+/// Determines the exponent of the shared scale of a block, given the
+/// exponent of the largest power of two of the element format.
+///
+/// Returns `None` if the block contains NaN or infinity.
+fn shared_exponent(v: &[f32; BLOCK_SIZE], emax: i32) -> Option<i32> {
+    let a = v.iter().map(|x| x.to_bits() & !0 >> 1).max().unwrap(); // Largest magnitude.
+    let e = if a < 1 << 23 { // Subnormal (or zero)
+        (31 - a.leading_zeros() as i32) - 149
+    } else if a < 255 << 23 { // Normal
+        (a >> 23) as i32 - 127
+    } else { // NaN or inf
+        return None;
+    };
+    Some((e - emax).max(-127))
+}
+
+/// Multiplies the bits of a finite non-zero f64 by 2^e, as long as the result
+/// stays within the normal range. Zero, infinity and NaN are kept as is.
+fn scale_f64(f: u64, e: i32) -> u64 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a == 0 || a >= 2047 << 52 {
+        f
+    } else {
+        f.wrapping_add((e as i64 as u64) << 52)
+    }
+}
+
+fn encode<T: Copy + Default>(v: &[f32; BLOCK_SIZE], emax: i32, element: impl Fn(u64) -> T) -> (u8, [T; BLOCK_SIZE]) {
+    let mut p = [T::default(); BLOCK_SIZE];
+    match shared_exponent(v, emax) {
+        Some(x) => {
+            for (p, v) in p.iter_mut().zip(v) {
+                *p = element(scale_f64(soft::f32_to_f64(v.to_bits()), -x)); // Exact, so only the element rounds.
+            }
+            ((x + 127) as u8, p)
+        }
+        None => (255, p),
+    }
+}
+
+fn decode<T: Copy>(scale: u8, p: &[T; BLOCK_SIZE], element: impl Fn(T) -> u64) -> [f32; BLOCK_SIZE] {
+    let mut v = [f32::NAN; BLOCK_SIZE];
+    if scale != 255 {
+        for (v, &p) in v.iter_mut().zip(p) {
+            *v = f32::from_bits(soft::f64_to_f32(scale_f64(element(p), scale as i32 - 127)));
+        }
+    }
+    v
+}
+
+// The FP6 and FP4 elements are the minifloat formats of the same name, which
+// round the same way and clamp to the largest value on overflow.
+
+fn f64_to_e3m2(f: u64) -> u8 {
+    minifloat::from_f64::<minifloat::F6E3M2>(f) as u8
+}
+
+fn e3m2_to_f64(p: u8) -> u64 {
+    minifloat::to_f64::<minifloat::F6E3M2>(p.into())
+}
+
+fn f64_to_e2m3(f: u64) -> u8 {
+    minifloat::from_f64::<minifloat::F6E2M3>(f) as u8
+}
+
+fn e2m3_to_f64(p: u8) -> u64 {
+    minifloat::to_f64::<minifloat::F6E2M3>(p.into())
+}
+
+fn f64_to_e2m1(f: u64) -> u8 {
+    minifloat::from_f64::<minifloat::F4E2M1>(f) as u8
+}
+
+fn e2m1_to_f64(p: u8) -> u64 {
+    minifloat::to_f64::<minifloat::F4E2M1>(p.into())
+}
+
+// INT8 elements are fixed point numbers with six fractional bits.
+
+fn f64_to_int8(f: u64) -> i8 {
+    soft::f64_to_i16_fixed(f, 6, Round::TiesToEven).clamp(-128, 127) as i8 // -128 is allowed, 128 is not.
+}
+
+fn int8_to_f64(p: i8) -> u64 {
+    soft::i16_fixed_to_f64(p.into(), 6)
+}
+
+macro_rules! impl_mx {
+    ($encode:ident $decode:ident $t:tt $format:literal, $emax:literal, $to_element:expr, $from_element:expr) => {
+        #[doc = concat!("Converts a block of `f32` values to ", $format, ".")]
+        pub fn $encode(v: &[f32; BLOCK_SIZE]) -> (u8, [$t; BLOCK_SIZE]) {
+            encode(v, $emax, $to_element)
+        }
+
+        #[doc = concat!("Converts a block of ", $format, " to `f32` values.")]
+        pub fn $decode(scale: u8, p: &[$t; BLOCK_SIZE]) -> [f32; BLOCK_SIZE] {
+            decode(scale, p, $from_element)
+        }
+    };
+}
+
+impl_mx!(f32_to_mxfp8_e4m3 mxfp8_e4m3_to_f32 u8 "MXFP8 (E4M3)", 8, soft::f64_to_f8e4m3_saturate, soft::f8e4m3_to_f64);
+impl_mx!(f32_to_mxfp8_e5m2 mxfp8_e5m2_to_f32 u8 "MXFP8 (E5M2)", 15, soft::f64_to_f8e5m2_saturate, soft::f8e5m2_to_f64);
+impl_mx!(f32_to_mxfp6_e3m2 mxfp6_e3m2_to_f32 u8 "MXFP6 (E3M2)", 4, f64_to_e3m2, e3m2_to_f64);
+impl_mx!(f32_to_mxfp6_e2m3 mxfp6_e2m3_to_f32 u8 "MXFP6 (E2M3)", 2, f64_to_e2m3, e2m3_to_f64);
+impl_mx!(f32_to_mxfp4 mxfp4_to_f32 u8 "MXFP4 (E2M1)", 2, f64_to_e2m1, e2m1_to_f64);
+impl_mx!(f32_to_mxint8 mxint8_to_f32 i8 "MXINT8", 0, f64_to_int8, int8_to_f64);
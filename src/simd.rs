@@ -0,0 +1,231 @@
+//! Lane-wise conversion functions on portable SIMD vectors.
+//!
+//! Only available with the `simd` feature, which requires a nightly compiler.
+//!
+//! These use the same algorithms as the [`soft`](crate::soft) implementations,
+//! applied to all lanes at once. Conversions that are cheap or natively
+//! available through [`Simd::cast`] are not included.
+//!
+//! There are no 128-bit lanes, so 128-bit integers are represented as a pair
+//! of vectors: one with the high 64 bits, and one with the low 64 bits.
+
+use core::simd::prelude::*;
+
+#[inline]
+pub fn u64_to_f32<const N: usize>(x: Simd<u64, N>) -> Simd<f32, N> {
+    let n = x.leading_zeros();
+    let y = x << n;
+    let a = (y >> 40).cast::<u32>(); // Significant bits, with bit 24 still in tact.
+    let b = (y >> 8 | y & Simd::splat(0xFFFF)).cast::<u32>(); // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let e = x.simd_eq(Simd::splat(0)).select(Simd::splat(0), Simd::splat(189) - n).cast::<u32>(); // Exponent plus 127, minus one, except for zero.
+    Simd::from_bits((e << 23) + m) // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[inline]
+pub fn u128_to_f32<const N: usize>((h, l): (Simd<u64, N>, Simd<u64, N>)) -> Simd<f32, N> {
+    let (n, yh, yl) = normalize(h, l);
+    let a = (yh >> 40).cast::<u32>(); // Significant bits, with bit 24 still in tact.
+    let s = (yh << 56 | yl).simd_ne(Simd::splat(0)).select(Simd::splat(1), Simd::splat(0));
+    let b = (yh >> 8 | s).cast::<u32>(); // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let z = (h | l).simd_eq(Simd::splat(0));
+    let e = z.select(Simd::splat(0), Simd::splat(253) - n).cast::<u32>(); // Exponent plus 127, minus one, except for zero.
+    Simd::from_bits((e << 23) + m) // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[inline]
+pub fn u64_to_f64<const N: usize>(x: Simd<u64, N>) -> Simd<f64, N> {
+    let n = x.leading_zeros();
+    let a = x << n >> 11; // Significant bits, with bit 53 still in tact.
+    let b = x << n << 53; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 63 & !a)) >> 63); // Add one when we need to round up. Break ties to even.
+    let e = x.simd_eq(Simd::splat(0)).select(Simd::splat(0), Simd::splat(1085) - n); // Exponent plus 1023, minus one, except for zero.
+    Simd::from_bits((e << 52) + m) // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[inline]
+pub fn u128_to_f64<const N: usize>((h, l): (Simd<u64, N>, Simd<u64, N>)) -> Simd<f64, N> {
+    let (n, yh, yl) = normalize(h, l);
+    let a = yh >> 11; // Significant bits, with bit 53 still in tact.
+    let b = yh << 53 | yl >> 11 | yl & Simd::splat(0xFFFF_FFFF); // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 63 & !a)) >> 63); // Add one when we need to round up. Break ties to even.
+    let z = (h | l).simd_eq(Simd::splat(0));
+    let e = z.select(Simd::splat(0), Simd::splat(1149) - n); // Exponent plus 1023, minus one, except for zero.
+    Simd::from_bits((e << 52) + m) // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[inline]
+pub fn i64_to_f32<const N: usize>(i: Simd<i64, N>) -> Simd<f32, N> {
+    let sign_bit = (i.cast::<u64>() >> 63 << 31).cast::<u32>();
+    Simd::from_bits(u64_to_f32(i.abs().cast()).to_bits() | sign_bit)
+}
+
+#[inline]
+pub fn i128_to_f32<const N: usize>((h, l): (Simd<i64, N>, Simd<u64, N>)) -> Simd<f32, N> {
+    let sign_bit = (h.cast::<u64>() >> 63 << 31).cast::<u32>();
+    Simd::from_bits(u128_to_f32(abs(h, l)).to_bits() | sign_bit)
+}
+
+#[inline]
+pub fn i64_to_f64<const N: usize>(i: Simd<i64, N>) -> Simd<f64, N> {
+    let sign_bit = i.cast::<u64>() >> 63 << 63;
+    Simd::from_bits(u64_to_f64(i.abs().cast()).to_bits() | sign_bit)
+}
+
+#[inline]
+pub fn i128_to_f64<const N: usize>((h, l): (Simd<i64, N>, Simd<u64, N>)) -> Simd<f64, N> {
+    let sign_bit = h.cast::<u64>() >> 63 << 63;
+    Simd::from_bits(u128_to_f64(abs(h, l)).to_bits() | sign_bit)
+}
+
+#[inline]
+pub fn f32_to_u64<const N: usize>(f: Simd<f32, N>) -> Simd<u64, N> {
+    let f = f.to_bits().cast::<u64>();
+    let m = Simd::splat(1 << 63) | f << 40; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(190) - (f >> 23); // Shift based on the exponent and bias.
+    let max = f.simd_le(Simd::splat(255 << 23)).select(Simd::splat(u64::MAX), Simd::splat(0)); // >= max (incl. inf), or negative or NaN.
+    let r = f.simd_lt(Simd::splat(191 << 23)).select(m >> s, max); // >= 1, < max
+    f.simd_lt(Simd::splat(127 << 23)).select(Simd::splat(0), r) // >= 0, < 1
+}
+
+#[inline]
+pub fn f32_to_u128<const N: usize>(f: Simd<f32, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let f = f.to_bits().cast::<u64>();
+    let m = Simd::splat(1 << 63) | f << 40; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(254) - (f >> 23); // Shift based on the exponent and bias.
+    let (h, l) = shr(m, s);
+    let max = f.simd_eq(Simd::splat(255 << 23)).select(Simd::splat(u64::MAX), Simd::splat(0)); // == inf, or negative or NaN.
+    let r = f.simd_lt(Simd::splat(255 << 23)); // >= 1, < inf
+    let h = r.select(h, max);
+    let l = r.select(l, max);
+    let z = f.simd_lt(Simd::splat(127 << 23)); // >= 0, < 1
+    (z.select(Simd::splat(0), h), z.select(Simd::splat(0), l))
+}
+
+#[inline]
+pub fn f64_to_u64<const N: usize>(f: Simd<f64, N>) -> Simd<u64, N> {
+    let f = f.to_bits();
+    let m = Simd::splat(1 << 63) | f << 11; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(1086) - (f >> 52); // Shift based on the exponent and bias.
+    let max = f.simd_le(Simd::splat(2047 << 52)).select(Simd::splat(u64::MAX), Simd::splat(0)); // >= max (incl. inf), or negative or NaN.
+    let r = f.simd_lt(Simd::splat(1087 << 52)).select(m >> s, max); // >= 1, < max
+    f.simd_lt(Simd::splat(1023 << 52)).select(Simd::splat(0), r) // >= 0, < 1
+}
+
+#[inline]
+pub fn f64_to_u128<const N: usize>(f: Simd<f64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let f = f.to_bits();
+    let m = Simd::splat(1 << 63) | f << 11; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(1150) - (f >> 52); // Shift based on the exponent and bias.
+    let (h, l) = shr(m, s);
+    let max = f.simd_le(Simd::splat(2047 << 52)).select(Simd::splat(u64::MAX), Simd::splat(0)); // >= max (incl. inf), or negative or NaN.
+    let r = f.simd_lt(Simd::splat(1151 << 52)); // >= 1, < max
+    let h = r.select(h, max);
+    let l = r.select(l, max);
+    let z = f.simd_lt(Simd::splat(1023 << 52)); // >= 0, < 1
+    (z.select(Simd::splat(0), h), z.select(Simd::splat(0), l))
+}
+
+#[inline]
+pub fn f32_to_i64<const N: usize>(f: Simd<f32, N>) -> Simd<i64, N> {
+    let f = f.to_bits().cast::<u64>();
+    let a = f & Simd::splat(0x7FFF_FFFF); // Remove sign bit.
+    let neg = f.simd_ge(Simd::splat(1 << 31));
+    let m = Simd::splat(1 << 63) | a << 40; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(190) - (a >> 23); // Shift based on the exponent and bias.
+    let u = (m >> s).cast::<i64>(); // Unsigned result.
+    let max = neg.select(Simd::splat(i64::MIN), Simd::splat(i64::MAX));
+    let max = a.simd_le(Simd::splat(255 << 23)).select(max, Simd::splat(0)); // >= max (incl. inf), or NaN.
+    let r = a.simd_lt(Simd::splat(190 << 23)).select(neg.select(-u, u), max); // >= 1, < max
+    a.simd_lt(Simd::splat(127 << 23)).select(Simd::splat(0), r) // >= 0, < 1
+}
+
+#[inline]
+pub fn f32_to_i128<const N: usize>(f: Simd<f32, N>) -> (Simd<i64, N>, Simd<u64, N>) {
+    let f = f.to_bits().cast::<u64>();
+    let a = f & Simd::splat(0x7FFF_FFFF); // Remove sign bit.
+    let neg = f.simd_ge(Simd::splat(1 << 31));
+    let m = Simd::splat(1 << 63) | a << 40; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(254) - (a >> 23); // Shift based on the exponent and bias.
+    let (h, l) = shr(m, s); // Unsigned result.
+    let (nh, nl) = neg128(h, l);
+    let (h, l) = (neg.select(nh, h), neg.select(nl, l));
+    let max_h = neg.select(Simd::splat(1 << 63), Simd::splat(!0 >> 1));
+    let max_l = neg.select(Simd::splat(0), Simd::splat(!0));
+    let nan = a.simd_gt(Simd::splat(255 << 23)); // NaN
+    let (max_h, max_l) = (nan.select(Simd::splat(0), max_h), nan.select(Simd::splat(0), max_l)); // >= max (incl. inf)
+    let r = a.simd_lt(Simd::splat(254 << 23)); // >= 1, < max
+    let (h, l) = (r.select(h, max_h), r.select(l, max_l));
+    let z = a.simd_lt(Simd::splat(127 << 23)); // >= 0, < 1
+    (z.select(Simd::splat(0), h).cast(), z.select(Simd::splat(0), l))
+}
+
+#[inline]
+pub fn f64_to_i64<const N: usize>(f: Simd<f64, N>) -> Simd<i64, N> {
+    let f = f.to_bits();
+    let a = f & Simd::splat(!0 >> 1); // Remove sign bit.
+    let neg = f.cast::<i64>().simd_lt(Simd::splat(0));
+    let m = Simd::splat(1 << 63) | a << 11; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(1086) - (a >> 52); // Shift based on the exponent and bias.
+    let u = (m >> s).cast::<i64>(); // Unsigned result.
+    let max = neg.select(Simd::splat(i64::MIN), Simd::splat(i64::MAX));
+    let max = a.simd_le(Simd::splat(2047 << 52)).select(max, Simd::splat(0)); // >= max (incl. inf), or NaN.
+    let r = a.simd_lt(Simd::splat(1086 << 52)).select(neg.select(-u, u), max); // >= 1, < max
+    a.simd_lt(Simd::splat(1023 << 52)).select(Simd::splat(0), r) // >= 0, < 1
+}
+
+#[inline]
+pub fn f64_to_i128<const N: usize>(f: Simd<f64, N>) -> (Simd<i64, N>, Simd<u64, N>) {
+    let f = f.to_bits();
+    let a = f & Simd::splat(!0 >> 1); // Remove sign bit.
+    let neg = f.simd_ge(Simd::splat(1 << 63));
+    let m = Simd::splat(1 << 63) | a << 11; // Mantissa and the implicit 1-bit.
+    let s = Simd::splat(1150) - (a >> 52); // Shift based on the exponent and bias.
+    let (h, l) = shr(m, s); // Unsigned result.
+    let (nh, nl) = neg128(h, l);
+    let (h, l) = (neg.select(nh, h), neg.select(nl, l));
+    let max_h = neg.select(Simd::splat(1 << 63), Simd::splat(!0 >> 1));
+    let max_l = neg.select(Simd::splat(0), Simd::splat(!0));
+    let nan = a.simd_gt(Simd::splat(2047 << 52)); // NaN
+    let (max_h, max_l) = (nan.select(Simd::splat(0), max_h), nan.select(Simd::splat(0), max_l)); // >= max (incl. inf)
+    let r = a.simd_lt(Simd::splat(1150 << 52)); // >= 1, < max
+    let (h, l) = (r.select(h, max_h), r.select(l, max_l));
+    let z = a.simd_lt(Simd::splat(1023 << 52)); // >= 0, < 1
+    (z.select(Simd::splat(0), h).cast(), z.select(Simd::splat(0), l))
+}
+
+/// Shifts a 128-bit value left until its highest bit is set. Returns the shift
+/// amount, and the high and low halves of the shifted value.
+#[inline]
+fn normalize<const N: usize>(h: Simd<u64, N>, l: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>, Simd<u64, N>) {
+    let z = h.simd_eq(Simd::splat(0));
+    let (h, l, n) = (z.select(l, h), z.select(Simd::splat(0), l), z.select(Simd::splat(64), Simd::splat(0)));
+    let k = h.leading_zeros();
+    let h = h << k | l >> 1 >> (Simd::splat(63) - k); // Two shifts, to avoid shifting by 64.
+    (n + k, h, l << k)
+}
+
+/// Shifts `m << 64` right by `s` (which is less than 128) bits.
+#[inline]
+fn shr<const N: usize>(m: Simd<u64, N>, s: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let small = s.simd_lt(Simd::splat(64));
+    let h = small.select(m >> s, Simd::splat(0));
+    let l = small.select(m << 1 << (Simd::splat(63) - s), m >> (s - Simd::splat(64))); // Two shifts, to avoid shifting by 64.
+    (h, l)
+}
+
+/// Negates a 128-bit value, wrapping around.
+#[inline]
+fn neg128<const N: usize>(h: Simd<u64, N>, l: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let carry = l.simd_eq(Simd::splat(0)).select(Simd::splat(1), Simd::splat(0));
+    (!h + carry, Simd::splat(0) - l)
+}
+
+/// The absolute value of a signed 128-bit value, as an unsigned value.
+#[inline]
+fn abs<const N: usize>(h: Simd<i64, N>, l: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let (nh, nl) = neg128(h.cast(), l);
+    let neg = h.simd_lt(Simd::splat(0));
+    (neg.select(nh, h.cast()), neg.select(nl, l))
+}
@@ -6,6 +6,67 @@
 //! To avoid using any floating point instructions or registers, all functions
 //! in this module take or return the bits of the floating point value as `u32`
 //! or `u64` instead of `f32` or `f64`.
+//!
+//! This module also provides conversions to and from IEEE 754 half precision
+//! floating point numbers (`f16`) and bfloat16 numbers (`bf16`), which are
+//! both represented as `u16`, and IEEE 754 quadruple precision floating point
+//! numbers (`f128`), which are represented as `u128`. The 8-bit OCP formats
+//! E4M3 (`f8e4m3`) and E5M2 (`f8e5m2`) are represented as `u8`.
+//!
+//! Conversions to TensorFloat-32 (`tf32`) result in the bits of an `f32` with
+//! the lowest 13 bits of the mantissa cleared, since that is how TF32 values
+//! are stored. The 24-bit format `fp24` (7 exponent bits, 16 mantissa bits,
+//! with subnormals, infinity and NaN like IEEE 754) is represented as the
+//! lowest 24 bits of a `u32`.
+//!
+//! IBM System/360 hexadecimal floating point numbers are represented as `u32`
+//! (`hfp32`, single precision) or `u64` (`hfp64`, double precision). They have
+//! no infinity or NaN: infinity converts to the largest value, and NaN to zero.
+//! Conversions from other formats result in a normalized number, unless it is
+//! too small to be normalized.
+//!
+//! VAX F_floating (`vaxf`), D_floating (`vaxd`) and G_floating (`vaxg`)
+//! numbers are represented as `u32` or `u64` in the order they are stored in
+//! memory (when read as little endian), with their 16-bit words swapped
+//! compared to the logical order of the bits. Reserved operands convert to
+//! NaN, and NaN converts to a reserved operand. Values too large for a VAX
+//! format (including infinity) result in the largest value, and values too
+//! small result in zero.
+//!
+//! Microsoft Binary Format numbers are represented as `u32` (`mbf32`) or
+//! `u64` (`mbf40` in the lowest 40 bits, and `mbf64`), as they are stored in
+//! memory when read as little endian: the exponent in the most significant
+//! byte, followed by the sign bit and the mantissa. An exponent of zero means
+//! zero, regardless of the other bits. MBF has no infinity or NaN: infinity
+//! and values too large convert to the largest value, and NaN and values too
+//! small to zero. Conversions to MBF that can overflow also have a `_checked`
+//! variant, which returns `None` in that case (and for infinity and NaN). All
+//! MBF numbers are within the range of `f32`, so conversions from MBF never
+//! overflow.
+//!
+//! Posits (`posit8`, `posit16` and `posit32`, as defined by the 2022 Posit
+//! Standard, with two exponent bits) are represented as `u8`, `u16` and
+//! `u32`. Conversions to posits round to the closest posit (breaking ties to
+//! even), but never round to zero or overflow: values too large or too small
+//! result in the largest or smallest posit. Infinity and NaN convert to NaR.
+//! NaR converts to NaN, or to zero for integers.
+//!
+//! Fixed point numbers (such as Q15, Q31, Q16.16, Q32.32 and Q64.64) are
+//! represented as integers with a number of fractional bits given by the
+//! `frac` argument, so their value is the integer multiplied by 2^−`frac`.
+//! Conversions from fixed point round to the closest float, breaking ties to
+//! even. Conversions to fixed point round according to the given [`Round`]
+//! mode, and saturate like the conversions to integers.
+//!
+//! The x87 80-bit extended precision format (`f80`) is represented as a
+//! `(u64, u16)` pair: the 64-bit significand (including the explicit integer
+//! bit) and the sign and exponent. Encodings that x87 hardware considers
+//! invalid (such as unnormals and pseudo-infinities) are treated as NaN.
+
+use crate::minifloat;
+use crate::Round;
+use core::cmp::Ordering;
+use core::num::FpCategory;
 
 #[cfg_attr(not(noinline), inline)]
 pub fn u8_to_f32(x: u8) -> u32 {
@@ -474,3 +535,4879 @@ pub fn f64_to_i128(f: u64) -> i128 {
         0
     }
 }
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f16(x: u8) -> u16 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let m = (x as u16) << (3 + n); // Significant bits, with bit 11 still in tact.
+    let e = 21 - n as u16; // Exponent plus 15, minus one.
+    (e << 10) + m // Bit 11 of m will overflow into e.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f16(x: u16) -> u16 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 5; // Significant bits, with bit 11 still in tact.
+    let b = x << n << 11; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 15 & !a)) >> 15); // Add one when we need to round up. Break ties to even.
+    let e = 29 - n as u16; // Exponent plus 15, minus one.
+    (e << 10) + m // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f16(x: u32) -> u16 {
+    if x < 65520 { // Anything above rounds to infinity.
+        u16_to_f16(x as u16)
+    } else {
+        31 << 10
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f16(x: u64) -> u16 {
+    if x < 65520 { // Anything above rounds to infinity.
+        u16_to_f16(x as u16)
+    } else {
+        31 << 10
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f16(x: u128) -> u16 {
+    if x < 65520 { // Anything above rounds to infinity.
+        u16_to_f16(x as u16)
+    } else {
+        31 << 10
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f16(i: i8) -> u16 {
+    let sign_bit = ((i >> 7) as u16) << 15;
+    u8_to_f16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f16(i: i16) -> u16 {
+    let sign_bit = ((i >> 15) as u16) << 15;
+    u16_to_f16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f16(i: i32) -> u16 {
+    let sign_bit = ((i >> 31) as u16) << 15;
+    u32_to_f16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f16(i: i64) -> u16 {
+    let sign_bit = ((i >> 63) as u16) << 15;
+    u64_to_f16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f16(i: i128) -> u16 {
+    let sign_bit = ((i >> 127) as u16) << 15;
+    u128_to_f16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_u8(f: u16) -> u8 {
+    if f < 15 << 10 { // >= 0, < 1
+        0
+    } else if f < 23 << 10 { // >= 1, < max
+        let m = 1 << 7 | (f >> 3) as u8; // Mantissa and the implicit 1-bit.
+        let s = 22 - (f >> 10); // Shift based on the exponent and bias.
+        m >> s
+    } else if f <= 31 << 10 { // >= max (incl. inf)
+        u8::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_u16(f: u16) -> u16 {
+    if f < 15 << 10 { // >= 0, < 1
+        0
+    } else if f < 31 << 10 { // >= 1, < inf
+        let m = 1 << 15 | f << 5; // Mantissa and the implicit 1-bit.
+        let s = 30 - (f >> 10); // Shift based on the exponent and bias.
+        m >> s
+    } else if f == 31 << 10 { // == inf
+        u16::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_u32(f: u16) -> u32 {
+    if f == 31 << 10 { // == inf
+        u32::MAX
+    } else { // Everything else fits in 16 bits.
+        f16_to_u16(f).into()
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_u64(f: u16) -> u64 {
+    if f == 31 << 10 { // == inf
+        u64::MAX
+    } else { // Everything else fits in 16 bits.
+        f16_to_u16(f).into()
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_u128(f: u16) -> u128 {
+    if f == 31 << 10 { // == inf
+        u128::MAX
+    } else { // Everything else fits in 16 bits.
+        f16_to_u16(f).into()
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_i8(f: u16) -> i8 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 15 << 10 { // >= 0, < 1
+        0
+    } else if a < 22 << 10 { // >= 1, < max
+        let m = 1 << 7 | (a >> 3) as u8; // Mantissa and the implicit 1-bit.
+        let s = 22 - (a >> 10); // Shift based on the exponent and bias.
+        let u = (m >> s) as i8; // Unsigned result.
+        if (f as i16) < 0 { -u } else { u }
+    } else if a <= 31 << 10 { // >= max (incl. inf)
+        if (f as i16) < 0 { i8::MIN } else { i8::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_i16(f: u16) -> i16 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 15 << 10 { // >= 0, < 1
+        0
+    } else if a < 30 << 10 { // >= 1, < max
+        let m = 1 << 15 | a << 5; // Mantissa and the implicit 1-bit.
+        let s = 30 - (a >> 10); // Shift based on the exponent and bias.
+        let u = (m >> s) as i16; // Unsigned result.
+        if (f as i16) < 0 { -u } else { u }
+    } else if a <= 31 << 10 { // >= max (incl. inf)
+        if (f as i16) < 0 { i16::MIN } else { i16::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_i32(f: u16) -> i32 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 31 << 10 { // Finite, fits in 16 bits.
+        let u = f16_to_u16(a) as i32; // Unsigned result.
+        if (f as i16) < 0 { -u } else { u }
+    } else if a == 31 << 10 { // == inf
+        if (f as i16) < 0 { i32::MIN } else { i32::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_i64(f: u16) -> i64 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 31 << 10 { // Finite, fits in 16 bits.
+        let u = f16_to_u16(a) as i64; // Unsigned result.
+        if (f as i16) < 0 { -u } else { u }
+    } else if a == 31 << 10 { // == inf
+        if (f as i16) < 0 { i64::MIN } else { i64::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_i128(f: u16) -> i128 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 31 << 10 { // Finite, fits in 16 bits.
+        let u = f16_to_u16(a) as i128; // Unsigned result.
+        if (f as i16) < 0 { -u } else { u }
+    } else if a == 31 << 10 { // == inf
+        if (f as i16) < 0 { i128::MIN } else { i128::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_bf16(x: u8) -> u16 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let m = (x as u16) << n; // Significant bits, with bit 8 still in tact.
+    let e = 133 - n as u16; // Exponent plus 127, minus one.
+    (e << 7) + m // Bit 8 of m will overflow into e.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_bf16(x: u16) -> u16 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 8; // Significant bits, with bit 8 still in tact.
+    let b = x << n << 8; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 15 & !a)) >> 15); // Add one when we need to round up. Break ties to even.
+    let e = 141 - n as u16; // Exponent plus 127, minus one.
+    (e << 7) + m // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_bf16(x: u32) -> u16 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 24; // Significant bits, with bit 8 still in tact.
+    let b = x << n << 8; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let e = 157 - n; // Exponent plus 127, minus one.
+    ((e << 7) + m) as u16 // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_bf16(x: u64) -> u16 {
+    let n = x.leading_zeros();
+    let y = x.wrapping_shl(n);
+    let a = (y >> 56) as u32; // Significant bits, with bit 8 still in tact.
+    let b = (y >> 24 | y & 0xFF_FFFF) as u32; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let e = if x == 0 { 0 } else { 189 - n }; // Exponent plus 127, minus one, except for zero.
+    ((e << 7) + m) as u16 // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_bf16(x: u128) -> u16 {
+    let n = x.leading_zeros();
+    let y = x.wrapping_shl(n);
+    let a = (y >> 120) as u32; // Significant bits, with bit 8 still in tact.
+    let b = (y >> 88) as u32 | (y << 40 != 0) as u32; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let e = if x == 0 { 0 } else { 253 - n }; // Exponent plus 127, minus one, except for zero.
+    ((e << 7) + m) as u16 // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_bf16(i: i8) -> u16 {
+    let sign_bit = ((i >> 7) as u16) << 15;
+    u8_to_bf16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_bf16(i: i16) -> u16 {
+    let sign_bit = ((i >> 15) as u16) << 15;
+    u16_to_bf16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_bf16(i: i32) -> u16 {
+    let sign_bit = ((i >> 31) as u16) << 15;
+    u32_to_bf16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_bf16(i: i64) -> u16 {
+    let sign_bit = ((i >> 63) as u16) << 15;
+    u64_to_bf16(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_bf16(i: i128) -> u16 {
+    let sign_bit = ((i >> 127) as u16) << 15;
+    u128_to_bf16(i.unsigned_abs()) | sign_bit
+}
+
+// A bfloat16 is just an f32 with the lower 16 bits cut off, so converting
+// to an integer is exactly the same as for the corresponding f32.
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_u8(f: u16) -> u8 {
+    f32_to_u8(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_u16(f: u16) -> u16 {
+    f32_to_u16(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_u32(f: u16) -> u32 {
+    f32_to_u32(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_u64(f: u16) -> u64 {
+    f32_to_u64(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_u128(f: u16) -> u128 {
+    f32_to_u128(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_i8(f: u16) -> i8 {
+    f32_to_i8(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_i16(f: u16) -> i16 {
+    f32_to_i16(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_i32(f: u16) -> i32 {
+    f32_to_i32(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_i64(f: u16) -> i64 {
+    f32_to_i64(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_i128(f: u16) -> i128 {
+    f32_to_i128(bf16_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn bf16_to_f32(f: u16) -> u32 {
+    (f as u32) << 16
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_bf16(f: u32) -> u16 {
+    if f & !0 >> 1 > 255 << 23 { // NaN
+        (f >> 16) as u16 | 1 << 6 // Keep the sign and upper bits of the payload, but make it quiet.
+    } else {
+        let a = f >> 16; // Significant bits, including the sign and exponent.
+        let b = f << 16; // Insignificant bits, only relevant for rounding.
+        (a + ((b - (b >> 31 & !a)) >> 31)) as u16 // Can overflow into the exponent, or even into infinity.
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f128(x: u8) -> u128 {
+    u32_to_f128(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f128(x: u16) -> u128 {
+    u32_to_f128(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f128(x: u32) -> u128 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let m = (x as u128) << (81 + n); // Significant bits, with bit 113 still in tact.
+    let e = 16413 - n as u128; // Exponent plus 16383, minus one.
+    (e << 112) + m // Bit 113 of m will overflow into e.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f128(x: u64) -> u128 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let m = (x as u128) << (49 + n); // Significant bits, with bit 113 still in tact.
+    let e = 16445 - n as u128; // Exponent plus 16383, minus one.
+    (e << 112) + m // Bit 113 of m will overflow into e.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f128(x: u128) -> u128 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 15; // Significant bits, with bit 113 still in tact.
+    let b = x << n << 113; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 127 & !a)) >> 127); // Add one when we need to round up. Break ties to even.
+    let e = 16509 - n as u128; // Exponent plus 16383, minus one.
+    (e << 112) + m // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f128(i: i8) -> u128 {
+    let sign_bit = ((i >> 7) as u128) << 127;
+    u8_to_f128(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f128(i: i16) -> u128 {
+    let sign_bit = ((i >> 15) as u128) << 127;
+    u16_to_f128(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f128(i: i32) -> u128 {
+    let sign_bit = ((i >> 31) as u128) << 127;
+    u32_to_f128(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f128(i: i64) -> u128 {
+    let sign_bit = ((i >> 63) as u128) << 127;
+    u64_to_f128(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f128(i: i128) -> u128 {
+    let sign_bit = ((i >> 127) as u128) << 127;
+    u128_to_f128(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_u8(f: u128) -> u8 {
+    if f < 16383 << 112 { // >= 0, < 1
+        0
+    } else if f < 16391 << 112 { // >= 1, < max
+        let m = 1 << 7 | (f >> 105) as u8; // Mantissa and the implicit 1-bit.
+        let s = 16390 - (f >> 112); // Shift based on the exponent and bias.
+        m >> s
+    } else if f <= 32767 << 112 { // >= max (incl. inf)
+        u8::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_u16(f: u128) -> u16 {
+    if f < 16383 << 112 { // >= 0, < 1
+        0
+    } else if f < 16399 << 112 { // >= 1, < max
+        let m = 1 << 15 | (f >> 97) as u16; // Mantissa and the implicit 1-bit.
+        let s = 16398 - (f >> 112); // Shift based on the exponent and bias.
+        m >> s
+    } else if f <= 32767 << 112 { // >= max (incl. inf)
+        u16::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_u32(f: u128) -> u32 {
+    if f < 16383 << 112 { // >= 0, < 1
+        0
+    } else if f < 16415 << 112 { // >= 1, < max
+        let m = 1 << 31 | (f >> 81) as u32; // Mantissa and the implicit 1-bit.
+        let s = 16414 - (f >> 112); // Shift based on the exponent and bias.
+        m >> s
+    } else if f <= 32767 << 112 { // >= max (incl. inf)
+        u32::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_u64(f: u128) -> u64 {
+    if f < 16383 << 112 { // >= 0, < 1
+        0
+    } else if f < 16447 << 112 { // >= 1, < max
+        let m = 1 << 63 | (f >> 49) as u64; // Mantissa and the implicit 1-bit.
+        let s = 16446 - (f >> 112); // Shift based on the exponent and bias.
+        m >> s
+    } else if f <= 32767 << 112 { // >= max (incl. inf)
+        u64::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_u128(f: u128) -> u128 {
+    if f < 16383 << 112 { // >= 0, < 1
+        0
+    } else if f < 16511 << 112 { // >= 1, < max
+        let m = 1 << 127 | f << 15; // Mantissa and the implicit 1-bit.
+        let s = 16510 - (f >> 112); // Shift based on the exponent and bias.
+        m >> s
+    } else if f <= 32767 << 112 { // >= max (incl. inf)
+        u128::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_i8(f: u128) -> i8 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 16383 << 112 { // >= 0, < 1
+        0
+    } else if a < 16390 << 112 { // >= 1, < max
+        let m = 1 << 7 | (a >> 105) as u8; // Mantissa and the implicit 1-bit.
+        let s = 16390 - (a >> 112); // Shift based on the exponent and bias.
+        let u = (m >> s) as i8; // Unsigned result.
+        if (f as i128) < 0 { -u } else { u }
+    } else if a <= 32767 << 112 { // >= max (incl. inf)
+        if (f as i128) < 0 { i8::MIN } else { i8::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_i16(f: u128) -> i16 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 16383 << 112 { // >= 0, < 1
+        0
+    } else if a < 16398 << 112 { // >= 1, < max
+        let m = 1 << 15 | (a >> 97) as u16; // Mantissa and the implicit 1-bit.
+        let s = 16398 - (a >> 112); // Shift based on the exponent and bias.
+        let u = (m >> s) as i16; // Unsigned result.
+        if (f as i128) < 0 { -u } else { u }
+    } else if a <= 32767 << 112 { // >= max (incl. inf)
+        if (f as i128) < 0 { i16::MIN } else { i16::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_i32(f: u128) -> i32 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 16383 << 112 { // >= 0, < 1
+        0
+    } else if a < 16414 << 112 { // >= 1, < max
+        let m = 1 << 31 | (a >> 81) as u32; // Mantissa and the implicit 1-bit.
+        let s = 16414 - (a >> 112); // Shift based on the exponent and bias.
+        let u = (m >> s) as i32; // Unsigned result.
+        if (f as i128) < 0 { -u } else { u }
+    } else if a <= 32767 << 112 { // >= max (incl. inf)
+        if (f as i128) < 0 { i32::MIN } else { i32::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_i64(f: u128) -> i64 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 16383 << 112 { // >= 0, < 1
+        0
+    } else if a < 16446 << 112 { // >= 1, < max
+        let m = 1 << 63 | (a >> 49) as u64; // Mantissa and the implicit 1-bit.
+        let s = 16446 - (a >> 112); // Shift based on the exponent and bias.
+        let u = (m >> s) as i64; // Unsigned result.
+        if (f as i128) < 0 { -u } else { u }
+    } else if a <= 32767 << 112 { // >= max (incl. inf)
+        if (f as i128) < 0 { i64::MIN } else { i64::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_i128(f: u128) -> i128 {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 16383 << 112 { // >= 0, < 1
+        0
+    } else if a < 16510 << 112 { // >= 1, < max
+        let m = 1 << 127 | a << 15; // Mantissa and the implicit 1-bit.
+        let s = 16510 - (a >> 112); // Shift based on the exponent and bias.
+        let u = (m >> s) as i128; // Unsigned result.
+        if (f as i128) < 0 { -u } else { u }
+    } else if a <= 32767 << 112 { // >= max (incl. inf)
+        if (f as i128) < 0 { i128::MIN } else { i128::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f80(x: u8) -> (u64, u16) {
+    u64_to_f80(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f80(x: u16) -> (u64, u16) {
+    u64_to_f80(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f80(x: u32) -> (u64, u16) {
+    u64_to_f80(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f80(x: u64) -> (u64, u16) {
+    if x == 0 { return (0, 0); }
+    let n = x.leading_zeros();
+    let e = 16446 - n as u16; // Exponent plus 16383.
+    (x << n, e) // The integer bit is explicit, so no need to remove it.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f80(x: u128) -> (u64, u16) {
+    if x == 0 { return (0, 0); }
+    let n = x.leading_zeros();
+    let a = (x << n >> 64) as u64; // Significant bits, including the integer bit.
+    let b = (x << n) as u64; // Insignificant bits, only relevant for rounding.
+    let (m, c) = a.overflowing_add((b - (b >> 63 & !a)) >> 63); // Add one when we need to round up. Break ties to even.
+    let e = 16510 - n as u16 + c as u16; // Exponent plus 16383, plus one if the mantissa overflowed.
+    (m | (c as u64) << 63, e) // After overflowing, the mantissa is zero, except for the integer bit.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f80(i: i8) -> (u64, u16) {
+    let sign_bit = ((i >> 7) as u16) << 15;
+    let (m, e) = u8_to_f80(i.unsigned_abs());
+    (m, e | sign_bit)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f80(i: i16) -> (u64, u16) {
+    let sign_bit = ((i >> 15) as u16) << 15;
+    let (m, e) = u16_to_f80(i.unsigned_abs());
+    (m, e | sign_bit)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f80(i: i32) -> (u64, u16) {
+    let sign_bit = ((i >> 31) as u16) << 15;
+    let (m, e) = u32_to_f80(i.unsigned_abs());
+    (m, e | sign_bit)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f80(i: i64) -> (u64, u16) {
+    let sign_bit = ((i >> 63) as u16) << 15;
+    let (m, e) = u64_to_f80(i.unsigned_abs());
+    (m, e | sign_bit)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f80(i: i128) -> (u64, u16) {
+    let sign_bit = ((i >> 127) as u16) << 15;
+    let (m, e) = u128_to_f80(i.unsigned_abs());
+    (m, e | sign_bit)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_u8(f: (u64, u16)) -> u8 {
+    let (m, e) = f;
+    if e < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if e < 16391 { // >= 1, < max
+        (m >> (16446 - e)) as u8
+    } else if e < 32767 || e == 32767 && m << 1 == 0 { // >= max (incl. inf)
+        u8::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_u16(f: (u64, u16)) -> u16 {
+    let (m, e) = f;
+    if e < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if e < 16399 { // >= 1, < max
+        (m >> (16446 - e)) as u16
+    } else if e < 32767 || e == 32767 && m << 1 == 0 { // >= max (incl. inf)
+        u16::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_u32(f: (u64, u16)) -> u32 {
+    let (m, e) = f;
+    if e < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if e < 16415 { // >= 1, < max
+        (m >> (16446 - e)) as u32
+    } else if e < 32767 || e == 32767 && m << 1 == 0 { // >= max (incl. inf)
+        u32::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_u64(f: (u64, u16)) -> u64 {
+    let (m, e) = f;
+    if e < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if e < 16447 { // >= 1, < max
+        m >> (16446 - e)
+    } else if e < 32767 || e == 32767 && m << 1 == 0 { // >= max (incl. inf)
+        u64::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_u128(f: (u64, u16)) -> u128 {
+    let (m, e) = f;
+    if e < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if e < 16511 { // >= 1, < max
+        ((m as u128) << 64) >> (16510 - e)
+    } else if e < 32767 || e == 32767 && m << 1 == 0 { // >= max (incl. inf)
+        u128::MAX
+    } else { // Negative or NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_i8(f: (u64, u16)) -> i8 {
+    let (m, e) = f;
+    let a = e & !0 >> 1; // Remove sign bit.
+    if a < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if a < 16390 { // >= 1, < max
+        let u = (m >> (16446 - a)) as i8; // Unsigned result.
+        if (e as i16) < 0 { -u } else { u }
+    } else if a < 32767 || m << 1 == 0 { // >= max (incl. inf)
+        if (e as i16) < 0 { i8::MIN } else { i8::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_i16(f: (u64, u16)) -> i16 {
+    let (m, e) = f;
+    let a = e & !0 >> 1; // Remove sign bit.
+    if a < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if a < 16398 { // >= 1, < max
+        let u = (m >> (16446 - a)) as i16; // Unsigned result.
+        if (e as i16) < 0 { -u } else { u }
+    } else if a < 32767 || m << 1 == 0 { // >= max (incl. inf)
+        if (e as i16) < 0 { i16::MIN } else { i16::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_i32(f: (u64, u16)) -> i32 {
+    let (m, e) = f;
+    let a = e & !0 >> 1; // Remove sign bit.
+    if a < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if a < 16414 { // >= 1, < max
+        let u = (m >> (16446 - a)) as i32; // Unsigned result.
+        if (e as i16) < 0 { -u } else { u }
+    } else if a < 32767 || m << 1 == 0 { // >= max (incl. inf)
+        if (e as i16) < 0 { i32::MIN } else { i32::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_i64(f: (u64, u16)) -> i64 {
+    let (m, e) = f;
+    let a = e & !0 >> 1; // Remove sign bit.
+    if a < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if a < 16446 { // >= 1, < max
+        let u = (m >> (16446 - a)) as i64; // Unsigned result.
+        if (e as i16) < 0 { -u } else { u }
+    } else if a < 32767 || m << 1 == 0 { // >= max (incl. inf)
+        if (e as i16) < 0 { i64::MIN } else { i64::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f80_to_i128(f: (u64, u16)) -> i128 {
+    let (m, e) = f;
+    let a = e & !0 >> 1; // Remove sign bit.
+    if a < 16383 { // >= 0, < 1
+        0
+    } else if m >> 63 == 0 { // Missing integer bit: not a valid number.
+        0
+    } else if a < 16510 { // >= 1, < max
+        let u = (((m as u128) << 64) >> (16510 - a)) as i128; // Unsigned result.
+        if (e as i16) < 0 { -u } else { u }
+    } else if a < 32767 || m << 1 == 0 { // >= max (incl. inf)
+        if (e as i16) < 0 { i128::MIN } else { i128::MAX }
+    } else { // NaN
+        0
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f64(f: u32) -> u64 {
+    let sign_bit = ((f >> 31) as u64) << 63;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 23 { // Subnormal
+        let n = a.leading_zeros();
+        let m = (a as u64) << (n + 21); // Normalized mantissa, with bit 53 still in tact.
+        let e = 904 - n as u64; // Exponent plus 1023, minus one.
+        (e << 52) + m // Bit 53 of m will overflow into e.
+    } else if a < 255 << 23 { // Normal
+        ((a as u64) << 29) + (896 << 52) // Only the bias of the exponent changes.
+    } else if a == 255 << 23 { // Infinity
+        2047 << 52
+    } else { // NaN
+        2047 << 52 | 1 << 51 | (a as u64) << 29 // Keep the payload, but make it quiet.
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f32(f: u64) -> u32 {
+    let sign_bit = (f >> 32) as u32 & 1 << 31;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 873 << 52 { // < 2^-150, rounds to zero.
+        0
+    } else if a < 897 << 52 { // < 2^-126, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 52 | a & !0 >> 12; // Mantissa and the implicit 1-bit.
+        let s = 926 - (a >> 52); // Shift based on the exponent and bias, such that the unit is 2^-149.
+        let x = m >> s; // Significant bits.
+        let y = m << (64 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // Add one when we need to round up. Break ties to even.
+    } else if a < 1151 << 52 { // < 2^128, rounds to a normal number (or infinity).
+        let x = (a >> 29) - (896 << 23); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 35; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+    } else if a <= 2047 << 52 { // Overflow (incl. inf)
+        255 << 23
+    } else { // NaN
+        255 << 23 | 1 << 22 | a >> 29 & 0x7F_FFFF // Keep the upper bits of the payload, but make it quiet.
+    };
+    b as u32 | sign_bit
+}
+
+/// Returns the magnitude of `f`, rounded towards zero, and whether that was
+/// inexact. Ignores the sign bit.
+#[cfg_attr(not(noinline), inline)]
+fn f64_to_f32_truncated(f: u64) -> (u32, bool) {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 874 << 52 { // < 2^-149, truncates to zero.
+        (0, a != 0)
+    } else if a < 897 << 52 { // < 2^-126, truncates to a subnormal.
+        let m = 1 << 52 | a & !0 >> 12; // Mantissa and the implicit 1-bit.
+        let s = 926 - (a >> 52); // Shift based on the exponent and bias, such that the unit is 2^-149.
+        ((m >> s) as u32, m << (64 - s) != 0)
+    } else if a < 1151 << 52 { // < 2^128, truncates to a normal number.
+        let x = (a >> 29) - (896 << 23); // Significant bits, with the exponent adjusted for the new bias.
+        (x as u32, a << 35 != 0)
+    } else if a < 2047 << 52 { // Overflow
+        (0x7F7F_FFFF, true)
+    } else if a == 2047 << 52 { // Infinity
+        (255 << 23, false)
+    } else { // NaN
+        (255 << 23 | 1 << 22 | (a >> 29) as u32 & 0x7F_FFFF, false) // Keep the upper bits of the payload, but make it quiet.
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f32_truncate(f: u64) -> u32 {
+    let sign_bit = (f >> 32) as u32 & 1 << 31;
+    f64_to_f32_truncated(f).0 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f32_ceil(f: u64) -> u32 {
+    let sign_bit = (f >> 32) as u32 & 1 << 31;
+    let (m, inexact) = f64_to_f32_truncated(f);
+    (m + (inexact && sign_bit == 0) as u32) | sign_bit // Round the magnitude up for positive numbers.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f32_floor(f: u64) -> u32 {
+    let sign_bit = (f >> 32) as u32 & 1 << 31;
+    let (m, inexact) = f64_to_f32_truncated(f);
+    (m + (inexact && sign_bit != 0) as u32) | sign_bit // Round the magnitude up for negative numbers.
+}
+
+/// Returns the magnitude of `f`, rounded towards zero, and whether that was
+/// inexact. Ignores the sign bit.
+#[cfg_attr(not(noinline), inline)]
+fn f128_to_f64_truncated(f: u128) -> (u64, bool) {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 15309 << 112 { // < 2^-1074, truncates to zero.
+        (0, a != 0)
+    } else if a < 15361 << 112 { // < 2^-1022, truncates to a subnormal.
+        let m = 1 << 112 | a & !0 >> 16; // Mantissa and the implicit 1-bit.
+        let s = 15421 - (a >> 112); // Shift based on the exponent and bias, such that the unit is 2^-1074.
+        ((m >> s) as u64, m << (128 - s) != 0)
+    } else if a < 17407 << 112 { // < 2^1024, truncates to a normal number.
+        let x = (a >> 60) - (15360 << 52); // Significant bits, with the exponent adjusted for the new bias.
+        (x as u64, a << 68 != 0)
+    } else if a < 32767 << 112 { // Overflow
+        (0x7FEF_FFFF_FFFF_FFFF, true)
+    } else if a == 32767 << 112 { // Infinity
+        (2047 << 52, false)
+    } else { // NaN
+        (2047 << 52 | 1 << 51 | (a >> 60) as u64 & !0 >> 12, false) // Keep the upper bits of the payload, but make it quiet.
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_f64_truncate(f: u128) -> u64 {
+    let sign_bit = (f >> 64) as u64 & 1 << 63;
+    f128_to_f64_truncated(f).0 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_f64_ceil(f: u128) -> u64 {
+    let sign_bit = (f >> 64) as u64 & 1 << 63;
+    let (m, inexact) = f128_to_f64_truncated(f);
+    (m + (inexact && sign_bit == 0) as u64) | sign_bit // Round the magnitude up for positive numbers.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f128_to_f64_floor(f: u128) -> u64 {
+    let sign_bit = (f >> 64) as u64 & 1 << 63;
+    let (m, inexact) = f128_to_f64_truncated(f);
+    (m + (inexact && sign_bit != 0) as u64) | sign_bit // Round the magnitude up for negative numbers.
+}
+
+/// Returns the magnitude of `f`, rounded towards zero, and whether that was
+/// inexact. Ignores the sign bit.
+#[cfg_attr(not(noinline), inline)]
+fn f32_to_f16_truncated(f: u32) -> (u16, bool) {
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a < 103 << 23 { // < 2^-24, truncates to zero.
+        (0, a != 0)
+    } else if a < 113 << 23 { // < 2^-14, truncates to a subnormal.
+        let m = 1 << 23 | a & 0x7F_FFFF; // Mantissa and the implicit 1-bit.
+        let s = 126 - (a >> 23); // Shift based on the exponent and bias, such that the unit is 2^-24.
+        ((m >> s) as u16, m << (32 - s) != 0)
+    } else if a < 143 << 23 { // < 2^16, truncates to a normal number.
+        let x = (a >> 13) - (112 << 10); // Significant bits, with the exponent adjusted for the new bias.
+        (x as u16, a << 19 != 0)
+    } else if a < 255 << 23 { // Overflow
+        (0x7BFF, true)
+    } else if a == 255 << 23 { // Infinity
+        (31 << 10, false)
+    } else { // NaN
+        (31 << 10 | 1 << 9 | (a >> 13) as u16 & 0x3FF, false) // Keep the upper bits of the payload, but make it quiet.
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f16_truncate(f: u32) -> u16 {
+    let sign_bit = (f >> 16) as u16 & 1 << 15;
+    f32_to_f16_truncated(f).0 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f16_ceil(f: u32) -> u16 {
+    let sign_bit = (f >> 16) as u16 & 1 << 15;
+    let (m, inexact) = f32_to_f16_truncated(f);
+    (m + (inexact && sign_bit == 0) as u16) | sign_bit // Round the magnitude up for positive numbers.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f16_floor(f: u32) -> u16 {
+    let sign_bit = (f >> 16) as u16 & 1 << 15;
+    let (m, inexact) = f32_to_f16_truncated(f);
+    (m + (inexact && sign_bit != 0) as u16) | sign_bit // Round the magnitude up for negative numbers.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_f32(f: u16) -> u32 {
+    let sign_bit = ((f >> 15) as u32) << 31;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 10 { // Subnormal
+        let n = a.leading_zeros();
+        let m = (a as u32) << (n + 8); // Normalized mantissa, with bit 24 still in tact.
+        let e = 117 - n; // Exponent plus 127, minus one.
+        (e << 23) + m // Bit 24 of m will overflow into e.
+    } else if a < 31 << 10 { // Normal
+        ((a as u32) << 13) + (112 << 23) // Only the bias of the exponent changes.
+    } else if a == 31 << 10 { // Infinity
+        255 << 23
+    } else { // NaN
+        255 << 23 | 1 << 22 | (a as u32) << 13 // Keep the payload, but make it quiet.
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f16_to_f64(f: u16) -> u64 {
+    let sign_bit = ((f >> 15) as u64) << 63;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 10 { // Subnormal
+        let n = a.leading_zeros();
+        let m = (a as u64) << (n + 37); // Normalized mantissa, with bit 53 still in tact.
+        let e = 1013 - n as u64; // Exponent plus 1023, minus one.
+        (e << 52) + m // Bit 53 of m will overflow into e.
+    } else if a < 31 << 10 { // Normal
+        ((a as u64) << 42) + (1008 << 52) // Only the bias of the exponent changes.
+    } else if a == 31 << 10 { // Infinity
+        2047 << 52
+    } else { // NaN
+        2047 << 52 | 1 << 51 | (a as u64) << 42 // Keep the payload, but make it quiet.
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f16(f: u32) -> u16 {
+    let sign_bit = (f >> 16) as u16 & 1 << 15;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 102 << 23 { // < 2^-25, rounds to zero.
+        0
+    } else if a < 113 << 23 { // < 2^-14, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 23 | a & 0x7F_FFFF; // Mantissa and the implicit 1-bit.
+        let s = 126 - (a >> 23); // Shift based on the exponent and bias, such that the unit is 2^-24.
+        let x = m >> s; // Significant bits.
+        let y = m << (32 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // Add one when we need to round up. Break ties to even.
+    } else if a < 143 << 23 { // < 2^16, rounds to a normal number (or infinity).
+        let x = (a >> 13) - (112 << 10); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 19; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+    } else if a <= 255 << 23 { // Overflow (incl. inf)
+        31 << 10
+    } else { // NaN
+        31 << 10 | 1 << 9 | a >> 13 & 0x3FF // Keep the upper bits of the payload, but make it quiet.
+    };
+    b as u16 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f16(f: u64) -> u16 {
+    let sign_bit = (f >> 48) as u16 & 1 << 15;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 998 << 52 { // < 2^-25, rounds to zero.
+        0
+    } else if a < 1009 << 52 { // < 2^-14, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 52 | a & !0 >> 12; // Mantissa and the implicit 1-bit.
+        let s = 1051 - (a >> 52); // Shift based on the exponent and bias, such that the unit is 2^-24.
+        let x = m >> s; // Significant bits.
+        let y = m << (64 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // Add one when we need to round up. Break ties to even.
+    } else if a < 1039 << 52 { // < 2^16, rounds to a normal number (or infinity).
+        let x = (a >> 42) - (1008 << 10); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 22; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+    } else if a <= 2047 << 52 { // Overflow (incl. inf)
+        31 << 10
+    } else { // NaN
+        31 << 10 | 1 << 9 | a >> 42 & 0x3FF // Keep the upper bits of the payload, but make it quiet.
+    };
+    b as u16 | sign_bit
+}
+
+/// Rounds to E4M3, returning the result and whether it overflowed.
+///
+/// On overflow (including infinity), the result is NaN.
+#[cfg_attr(not(noinline), inline)]
+fn f32_to_f8e4m3_rounded(f: u32) -> (u8, bool) {
+    let sign_bit = (f >> 24) as u8 & 1 << 7;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 117 << 23 { // < 2^-10, rounds to zero.
+        0
+    } else if a < 121 << 23 { // < 2^-6, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 23 | a & 0x7F_FFFF; // Mantissa and the implicit 1-bit.
+        let s = 141 - (a >> 23); // Shift based on the exponent and bias, such that the unit is 2^-9.
+        let x = m >> s; // Significant bits.
+        let y = m << (32 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // Add one when we need to round up. Break ties to even.
+    } else if a < 136 << 23 { // < 2^9, rounds to a normal number (or overflows).
+        let x = (a >> 20) - (120 << 3); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 12; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // + not |, so the mantissa can overflow into the exponent.
+    } else if a <= 255 << 23 { // Overflow (incl. inf)
+        0x80
+    } else { // NaN
+        return (0x7F | sign_bit, false);
+    };
+    if b > 0x7E { // Above 448, which would need the NaN encoding (or more).
+        (0x7F | sign_bit, true)
+    } else {
+        (b as u8 | sign_bit, false)
+    }
+}
+
+/// Rounds to E4M3, returning the result and whether it overflowed.
+///
+/// On overflow (including infinity), the result is NaN.
+#[cfg_attr(not(noinline), inline)]
+fn f64_to_f8e4m3_rounded(f: u64) -> (u8, bool) {
+    let sign_bit = (f >> 56) as u8 & 1 << 7;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 1013 << 52 { // < 2^-10, rounds to zero.
+        0
+    } else if a < 1017 << 52 { // < 2^-6, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 52 | a & !0 >> 12; // Mantissa and the implicit 1-bit.
+        let s = 1066 - (a >> 52); // Shift based on the exponent and bias, such that the unit is 2^-9.
+        let x = m >> s; // Significant bits.
+        let y = m << (64 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // Add one when we need to round up. Break ties to even.
+    } else if a < 1032 << 52 { // < 2^9, rounds to a normal number (or overflows).
+        let x = (a >> 49) - (1016 << 3); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 15; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // + not |, so the mantissa can overflow into the exponent.
+    } else if a <= 2047 << 52 { // Overflow (incl. inf)
+        0x80
+    } else { // NaN
+        return (0x7F | sign_bit, false);
+    };
+    if b > 0x7E { // Above 448, which would need the NaN encoding (or more).
+        (0x7F | sign_bit, true)
+    } else {
+        (b as u8 | sign_bit, false)
+    }
+}
+
+/// Rounds to E5M2, returning the result and whether it overflowed.
+///
+/// On overflow (including infinity), the result is infinity.
+#[cfg_attr(not(noinline), inline)]
+fn f32_to_f8e5m2_rounded(f: u32) -> (u8, bool) {
+    let sign_bit = (f >> 24) as u8 & 1 << 7;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 110 << 23 { // < 2^-17, rounds to zero.
+        0
+    } else if a < 113 << 23 { // < 2^-14, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 23 | a & 0x7F_FFFF; // Mantissa and the implicit 1-bit.
+        let s = 134 - (a >> 23); // Shift based on the exponent and bias, such that the unit is 2^-16.
+        let x = m >> s; // Significant bits.
+        let y = m << (32 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // Add one when we need to round up. Break ties to even.
+    } else if a < 143 << 23 { // < 2^16, rounds to a normal number (or infinity).
+        let x = (a >> 21) - (112 << 2); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 11; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+    } else if a <= 255 << 23 { // Overflow (incl. inf)
+        31 << 2
+    } else { // NaN
+        return (31 << 2 | 1 << 1 | (a >> 21) as u8 & 1 | sign_bit, false); // Keep the upper bit of the payload, but make it quiet.
+    };
+    (b as u8 | sign_bit, b == 31 << 2)
+}
+
+/// Rounds to E5M2, returning the result and whether it overflowed.
+///
+/// On overflow (including infinity), the result is infinity.
+#[cfg_attr(not(noinline), inline)]
+fn f64_to_f8e5m2_rounded(f: u64) -> (u8, bool) {
+    let sign_bit = (f >> 56) as u8 & 1 << 7;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 1006 << 52 { // < 2^-17, rounds to zero.
+        0
+    } else if a < 1009 << 52 { // < 2^-14, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 52 | a & !0 >> 12; // Mantissa and the implicit 1-bit.
+        let s = 1059 - (a >> 52); // Shift based on the exponent and bias, such that the unit is 2^-16.
+        let x = m >> s; // Significant bits.
+        let y = m << (64 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // Add one when we need to round up. Break ties to even.
+    } else if a < 1039 << 52 { // < 2^16, rounds to a normal number (or infinity).
+        let x = (a >> 50) - (1008 << 2); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 14; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 63 & !x)) >> 63) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+    } else if a <= 2047 << 52 { // Overflow (incl. inf)
+        31 << 2
+    } else { // NaN
+        return (31 << 2 | 1 << 1 | (a >> 50) as u8 & 1 | sign_bit, false); // Keep the upper bit of the payload, but make it quiet.
+    };
+    (b as u8 | sign_bit, b == 31 << 2)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f8e4m3(f: u32) -> u8 {
+    f32_to_f8e4m3_rounded(f).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f8e4m3_saturate(f: u32) -> u8 {
+    match f32_to_f8e4m3_rounded(f) {
+        (r, true) => r - 1, // The largest finite value, with the same sign.
+        (r, false) => r,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f8e4m3(f: u64) -> u8 {
+    f64_to_f8e4m3_rounded(f).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f8e4m3_saturate(f: u64) -> u8 {
+    match f64_to_f8e4m3_rounded(f) {
+        (r, true) => r - 1, // The largest finite value, with the same sign.
+        (r, false) => r,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f8e5m2(f: u32) -> u8 {
+    f32_to_f8e5m2_rounded(f).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_f8e5m2_saturate(f: u32) -> u8 {
+    match f32_to_f8e5m2_rounded(f) {
+        (r, true) => r - 1, // The largest finite value, with the same sign.
+        (r, false) => r,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f8e5m2(f: u64) -> u8 {
+    f64_to_f8e5m2_rounded(f).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_f8e5m2_saturate(f: u64) -> u8 {
+    match f64_to_f8e5m2_rounded(f) {
+        (r, true) => r - 1, // The largest finite value, with the same sign.
+        (r, false) => r,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_f32(f: u8) -> u32 {
+    let sign_bit = ((f >> 7) as u32) << 31;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 3 { // Subnormal
+        let n = a.leading_zeros();
+        let m = (a as u32) << (n + 16); // Normalized mantissa, with bit 24 still in tact.
+        let e = 124 - n; // Exponent plus 127, minus one.
+        (e << 23) + m // Bit 24 of m will overflow into e.
+    } else if a < 0x7F { // Normal
+        ((a as u32) << 20) + (120 << 23) // Only the bias of the exponent changes.
+    } else { // NaN
+        255 << 23 | 1 << 22
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_f64(f: u8) -> u64 {
+    f32_to_f64(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_f32(f: u8) -> u32 {
+    f16_to_f32((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_f64(f: u8) -> u64 {
+    f16_to_f64((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f8e4m3(x: u8) -> u8 {
+    u16_to_f8e4m3(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f8e4m3(x: u16) -> u8 {
+    f32_to_f8e4m3(u16_to_f32(x)) // Lossless conversion to f32, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f8e4m3(x: u32) -> u8 {
+    u16_to_f8e4m3(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f8e4m3(x: u64) -> u8 {
+    u16_to_f8e4m3(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f8e4m3(x: u128) -> u8 {
+    u16_to_f8e4m3(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f8e4m3(i: i8) -> u8 {
+    let sign_bit = ((i >> 7) as u8) << 7;
+    u8_to_f8e4m3(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f8e4m3(i: i16) -> u8 {
+    let sign_bit = ((i >> 15) as u8) << 7;
+    u16_to_f8e4m3(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f8e4m3(i: i32) -> u8 {
+    let sign_bit = ((i >> 31) as u8) << 7;
+    u32_to_f8e4m3(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f8e4m3(i: i64) -> u8 {
+    let sign_bit = ((i >> 63) as u8) << 7;
+    u64_to_f8e4m3(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f8e4m3(i: i128) -> u8 {
+    let sign_bit = ((i >> 127) as u8) << 7;
+    u128_to_f8e4m3(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f8e4m3_saturate(x: u8) -> u8 {
+    u16_to_f8e4m3_saturate(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f8e4m3_saturate(x: u16) -> u8 {
+    f32_to_f8e4m3_saturate(u16_to_f32(x)) // Lossless conversion to f32, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f8e4m3_saturate(x: u32) -> u8 {
+    u16_to_f8e4m3_saturate(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f8e4m3_saturate(x: u64) -> u8 {
+    u16_to_f8e4m3_saturate(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f8e4m3_saturate(x: u128) -> u8 {
+    u16_to_f8e4m3_saturate(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f8e4m3_saturate(i: i8) -> u8 {
+    let sign_bit = ((i >> 7) as u8) << 7;
+    u8_to_f8e4m3_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f8e4m3_saturate(i: i16) -> u8 {
+    let sign_bit = ((i >> 15) as u8) << 7;
+    u16_to_f8e4m3_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f8e4m3_saturate(i: i32) -> u8 {
+    let sign_bit = ((i >> 31) as u8) << 7;
+    u32_to_f8e4m3_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f8e4m3_saturate(i: i64) -> u8 {
+    let sign_bit = ((i >> 63) as u8) << 7;
+    u64_to_f8e4m3_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f8e4m3_saturate(i: i128) -> u8 {
+    let sign_bit = ((i >> 127) as u8) << 7;
+    u128_to_f8e4m3_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_u8(f: u8) -> u8 {
+    f32_to_u8(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_u16(f: u8) -> u16 {
+    f32_to_u16(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_u32(f: u8) -> u32 {
+    f32_to_u32(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_u64(f: u8) -> u64 {
+    f32_to_u64(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_u128(f: u8) -> u128 {
+    f32_to_u128(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_i8(f: u8) -> i8 {
+    f32_to_i8(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_i16(f: u8) -> i16 {
+    f32_to_i16(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_i32(f: u8) -> i32 {
+    f32_to_i32(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_i64(f: u8) -> i64 {
+    f32_to_i64(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e4m3_to_i128(f: u8) -> i128 {
+    f32_to_i128(f8e4m3_to_f32(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f8e5m2(x: u8) -> u8 {
+    u16_to_f8e5m2(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f8e5m2(x: u16) -> u8 {
+    f32_to_f8e5m2(u16_to_f32(x)) // Lossless conversion to f32, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f8e5m2(x: u32) -> u8 {
+    u16_to_f8e5m2(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f8e5m2(x: u64) -> u8 {
+    u16_to_f8e5m2(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f8e5m2(x: u128) -> u8 {
+    u16_to_f8e5m2(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f8e5m2(i: i8) -> u8 {
+    let sign_bit = ((i >> 7) as u8) << 7;
+    u8_to_f8e5m2(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f8e5m2(i: i16) -> u8 {
+    let sign_bit = ((i >> 15) as u8) << 7;
+    u16_to_f8e5m2(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f8e5m2(i: i32) -> u8 {
+    let sign_bit = ((i >> 31) as u8) << 7;
+    u32_to_f8e5m2(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f8e5m2(i: i64) -> u8 {
+    let sign_bit = ((i >> 63) as u8) << 7;
+    u64_to_f8e5m2(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f8e5m2(i: i128) -> u8 {
+    let sign_bit = ((i >> 127) as u8) << 7;
+    u128_to_f8e5m2(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f8e5m2_saturate(x: u8) -> u8 {
+    u16_to_f8e5m2_saturate(x.into())
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f8e5m2_saturate(x: u16) -> u8 {
+    f32_to_f8e5m2_saturate(u16_to_f32(x)) // Lossless conversion to f32, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f8e5m2_saturate(x: u32) -> u8 {
+    u16_to_f8e5m2_saturate(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f8e5m2_saturate(x: u64) -> u8 {
+    u16_to_f8e5m2_saturate(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f8e5m2_saturate(x: u128) -> u8 {
+    u16_to_f8e5m2_saturate(x.min(u16::MAX.into()) as u16) // Anything above u16::MAX overflows anyway.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f8e5m2_saturate(i: i8) -> u8 {
+    let sign_bit = ((i >> 7) as u8) << 7;
+    u8_to_f8e5m2_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f8e5m2_saturate(i: i16) -> u8 {
+    let sign_bit = ((i >> 15) as u8) << 7;
+    u16_to_f8e5m2_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f8e5m2_saturate(i: i32) -> u8 {
+    let sign_bit = ((i >> 31) as u8) << 7;
+    u32_to_f8e5m2_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f8e5m2_saturate(i: i64) -> u8 {
+    let sign_bit = ((i >> 63) as u8) << 7;
+    u64_to_f8e5m2_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f8e5m2_saturate(i: i128) -> u8 {
+    let sign_bit = ((i >> 127) as u8) << 7;
+    u128_to_f8e5m2_saturate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_u8(f: u8) -> u8 {
+    f16_to_u8((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_u16(f: u8) -> u16 {
+    f16_to_u16((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_u32(f: u8) -> u32 {
+    f16_to_u32((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_u64(f: u8) -> u64 {
+    f16_to_u64((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_u128(f: u8) -> u128 {
+    f16_to_u128((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_i8(f: u8) -> i8 {
+    f16_to_i8((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_i16(f: u8) -> i16 {
+    f16_to_i16((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_i32(f: u8) -> i32 {
+    f16_to_i32((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_i64(f: u8) -> i64 {
+    f16_to_i64((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f8e5m2_to_i128(f: u8) -> i128 {
+    f16_to_i128((f as u16) << 8) // E5M2 is the upper half of f16.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_tf32(f: u32) -> u32 {
+    if f & !0 >> 1 > 255 << 23 { // NaN
+        f & !0x1FFF | 1 << 22 // Keep the sign and upper bits of the payload, but make it quiet.
+    } else {
+        let a = f >> 13; // Significant bits, including the sign and exponent.
+        let b = f << 19; // Insignificant bits, only relevant for rounding.
+        (a + ((b - (b >> 31 & !a)) >> 31)) << 13 // Can overflow into the exponent, or even into infinity.
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_tf32_truncate(f: u32) -> u32 {
+    if f & !0 >> 1 > 255 << 23 { // NaN
+        f & !0x1FFF | 1 << 22 // Keep the sign and upper bits of the payload, but make it quiet.
+    } else {
+        f & !0x1FFF
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_tf32(x: u8) -> u32 {
+    u8_to_f32(x) // Always fits in 11 bits.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_tf32(x: u16) -> u32 {
+    f32_to_tf32(u16_to_f32(x)) // Lossless conversion to f32, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_tf32(x: u32) -> u32 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 21; // Significant bits, with bit 11 still in tact.
+    let b = x << n << 11; // Insignificant bits, only relevant for rounding.
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let e = 157 - n; // Exponent plus 127, minus one.
+    (e << 23) + (m << 13) // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_tf32(x: u64) -> u32 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 53; // Significant bits, with bit 11 still in tact.
+    let b = x << n << 11; // Insignificant bits, only relevant for rounding.
+    let m = (a + ((b - (b >> 63 & !a)) >> 63)) as u32; // Add one when we need to round up. Break ties to even.
+    let e = 189 - n; // Exponent plus 127, minus one.
+    (e << 23) + (m << 13) // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_tf32(x: u128) -> u32 {
+    if x == 0 { return 0; }
+    let n = x.leading_zeros();
+    let a = x << n >> 117; // Significant bits, with bit 11 still in tact.
+    let b = x << n << 11; // Insignificant bits, only relevant for rounding.
+    let m = (a + ((b - (b >> 127 & !a)) >> 127)) as u32; // Add one when we need to round up. Break ties to even.
+    let e = 253 - n; // Exponent plus 127, minus one.
+    (e << 23) + (m << 13) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_tf32(i: i8) -> u32 {
+    let sign_bit = ((i >> 7) as u32) << 31;
+    u8_to_tf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_tf32(i: i16) -> u32 {
+    let sign_bit = ((i >> 15) as u32) << 31;
+    u16_to_tf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_tf32(i: i32) -> u32 {
+    let sign_bit = ((i >> 31) as u32) << 31;
+    u32_to_tf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_tf32(i: i64) -> u32 {
+    let sign_bit = ((i >> 63) as u32) << 31;
+    u64_to_tf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_tf32(i: i128) -> u32 {
+    let sign_bit = ((i >> 127) as u32) << 31;
+    u128_to_tf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_fp24(f: u32) -> u32 {
+    let sign_bit = f >> 8 & 1 << 23;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 48 << 23 { // < 2^-79, rounds to zero.
+        0
+    } else if a < 65 << 23 { // < 2^-62, rounds to a subnormal (or the smallest normal).
+        let m = 1 << 23 | a & 0x7F_FFFF; // Mantissa and the implicit 1-bit.
+        let s = 72 - (a >> 23); // Shift based on the exponent and bias, such that the unit is 2^-78.
+        let x = m >> s; // Significant bits.
+        let y = m << (32 - s); // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // Add one when we need to round up. Break ties to even.
+    } else if a < 191 << 23 { // < 2^64, rounds to a normal number (or infinity).
+        let x = (a >> 7) - (64 << 16); // Significant bits, with the exponent adjusted for the new bias.
+        let y = a << 25; // Insignificant bits, only relevant for rounding.
+        x + ((y - (y >> 31 & !x)) >> 31) // + not |, so the mantissa can overflow into the exponent, or even into infinity.
+    } else if a <= 255 << 23 { // Overflow (incl. inf)
+        127 << 16
+    } else { // NaN
+        127 << 16 | 1 << 15 | a >> 7 & 0x7FFF // Keep the upper bits of the payload, but make it quiet.
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_fp24_truncate(f: u32) -> u32 {
+    let sign_bit = f >> 8 & 1 << 23;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a < 49 << 23 { // < 2^-78, truncates to zero.
+        0
+    } else if a < 65 << 23 { // < 2^-62, truncates to a subnormal.
+        let m = 1 << 23 | a & 0x7F_FFFF; // Mantissa and the implicit 1-bit.
+        m >> (72 - (a >> 23)) // Shift based on the exponent and bias, such that the unit is 2^-78.
+    } else if a < 191 << 23 { // < 2^64, truncates to a normal number.
+        (a >> 7) - (64 << 16) // Significant bits, with the exponent adjusted for the new bias.
+    } else if a < 255 << 23 { // Overflow
+        0x7E_FFFF
+    } else if a == 255 << 23 { // Infinity
+        127 << 16
+    } else { // NaN
+        127 << 16 | 1 << 15 | a >> 7 & 0x7FFF // Keep the upper bits of the payload, but make it quiet.
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn fp24_to_f32(x: u32) -> u32 {
+    let sign_bit = (x & 1 << 23) << 8;
+    let a = x & 0x7F_FFFF; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 16 { // Subnormal
+        let n = a.leading_zeros();
+        let m = a << (n - 8); // Normalized mantissa, with bit 24 still in tact.
+        let e = 79 - n; // Exponent plus 127, minus one.
+        (e << 23) + m // Bit 24 of m will overflow into e.
+    } else if a < 127 << 16 { // Normal
+        (a << 7) + (64 << 23) // Only the bias of the exponent changes.
+    } else if a == 127 << 16 { // Infinity
+        255 << 23
+    } else { // NaN
+        255 << 23 | 1 << 22 | a << 7 // Keep the payload, but make it quiet.
+    };
+    b | sign_bit
+}
+
+/// Converts a non-zero IBM HFP fraction of `bits` bits and its exponent `x`
+/// to the bits of an f64, rounded towards zero. Also returns the
+/// insignificant bits, only relevant for rounding.
+#[cfg_attr(not(noinline), inline)]
+fn hfp_to_f64_truncated(f: u64, x: u64, bits: u32) -> (u64, u64) {
+    let n = f.leading_zeros();
+    let a = f << n >> 11; // Significant bits, with bit 53 still in tact.
+    let b = f << n << 53; // Insignificant bits, only relevant for rounding.
+    let e = 1085 - n as u64 + 4 * x - 256 - bits as u64; // Exponent plus 1023, minus one. Always in the normal range.
+    ((e << 52) + a, b)
+}
+
+/// Converts m × 2^e (with m > 0) to an IBM HFP magnitude with a fraction of
+/// `bits` bits, rounded to the closest value or towards zero.
+///
+/// Results that are too large saturate to the largest value. Results that
+/// are too small for a normalized number use the smallest exponent.
+#[cfg_attr(not(noinline), inline)]
+fn to_hfp(m: u128, e: i32, bits: u32, truncate: bool) -> u64 {
+    let max = !0 >> (57 - bits); // All bits set, except the sign bit.
+    let n = 127 - m.leading_zeros() as i32; // Position of the highest 1-bit.
+    let x = (((n + e) >> 2) + 65).max(0); // Exponent such that the value is < 16^(x - 64), if possible.
+    if x > 127 { return max; }
+    let s = 4 * (x - 64) - bits as i32 - e; // Number of insignificant bits.
+    let f = if s <= 0 {
+        (m << -s) as u64 // Exact.
+    } else if s > n + 1 {
+        0 // Less than half of the smallest step.
+    } else {
+        let a = m >> s; // Significant bits.
+        let b = m << (128 - s); // Insignificant bits, only relevant for rounding.
+        if truncate {
+            a as u64
+        } else {
+            (a + ((b - (b >> 127 & !a)) >> 127)) as u64 // Add one when we need to round up. Break ties to even.
+        }
+    };
+    if f >> bits == 0 {
+        (x as u64) << bits | f
+    } else if x < 127 { // Rounding overflowed into a new hexadecimal digit.
+        (x as u64 + 1) << bits | f >> 4
+    } else {
+        max
+    }
+}
+
+/// Truncates an IBM HFP fraction of `bits` bits with exponent `x` to an
+/// integer, saturated to `u128::MAX`.
+#[cfg_attr(not(noinline), inline)]
+fn hfp_to_u128(f: u64, x: u64, bits: u32) -> u128 {
+    let k = 4 * x as i32 - 256 - bits as i32; // Exponent of the unit of the fraction.
+    if k < 0 {
+        f.checked_shr(-k as u32).unwrap_or(0).into()
+    } else if k as u32 <= (f as u128).leading_zeros() {
+        (f as u128) << k
+    } else {
+        u128::MAX
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_f64(h: u32) -> u64 {
+    let sign_bit = ((h >> 31) as u64) << 63;
+    let f = (h & 0xFF_FFFF) as u64;
+    if f == 0 { return sign_bit; }
+    hfp_to_f64_truncated(f, (h >> 24 & 0x7F) as u64, 24).0 | sign_bit // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_f32(h: u32) -> u32 {
+    f64_to_f32(hfp32_to_f64(h)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_f32_truncate(h: u32) -> u32 {
+    f64_to_f32_truncate(hfp32_to_f64(h))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_f64(h: u64) -> u64 {
+    let sign_bit = h & 1 << 63;
+    let f = h & !0 >> 8;
+    if f == 0 { return sign_bit; }
+    let (a, b) = hfp_to_f64_truncated(f, h >> 56 & 0x7F, 56);
+    (a + ((b - (b >> 63 & !a)) >> 63)) | sign_bit // Add one when we need to round up. Break ties to even.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_f64_truncate(h: u64) -> u64 {
+    let sign_bit = h & 1 << 63;
+    let f = h & !0 >> 8;
+    if f == 0 { return sign_bit; }
+    hfp_to_f64_truncated(f, h >> 56 & 0x7F, 56).0 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_f32(h: u64) -> u32 {
+    let sign_bit = h & 1 << 63;
+    let f = h & !0 >> 8;
+    if f == 0 { return (sign_bit >> 32) as u32; }
+    let (a, b) = hfp_to_f64_truncated(f, h >> 56 & 0x7F, 56);
+    f64_to_f32(a | (b != 0) as u64 | sign_bit) // Round to odd first, to avoid rounding twice.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_f32_truncate(h: u64) -> u32 {
+    f64_to_f32_truncate(hfp64_to_f64_truncate(h))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_hfp32(f: u64) -> u32 {
+    let sign_bit = (f >> 32) as u32 & 1 << 31;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_hfp(a.into(), -1074, 24, false)
+    } else if a < 2047 << 52 { // Normal
+        to_hfp((a & !0 >> 12 | 1 << 52).into(), (a >> 52) as i32 - 1075, 24, false)
+    } else if a == 2047 << 52 { // Infinity
+        0x7FFF_FFFF // The largest value.
+    } else { // NaN
+        return 0;
+    };
+    b as u32 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_hfp32_truncate(f: u64) -> u32 {
+    let sign_bit = (f >> 32) as u32 & 1 << 31;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_hfp(a.into(), -1074, 24, true)
+    } else if a < 2047 << 52 { // Normal
+        to_hfp((a & !0 >> 12 | 1 << 52).into(), (a >> 52) as i32 - 1075, 24, true)
+    } else if a == 2047 << 52 { // Infinity
+        0x7FFF_FFFF // The largest value.
+    } else { // NaN
+        return 0;
+    };
+    b as u32 | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_hfp64(f: u64) -> u64 {
+    let sign_bit = f & 1 << 63;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_hfp(a.into(), -1074, 56, false)
+    } else if a < 2047 << 52 { // Normal
+        to_hfp((a & !0 >> 12 | 1 << 52).into(), (a >> 52) as i32 - 1075, 56, false)
+    } else if a == 2047 << 52 { // Infinity
+        !0 >> 1 // The largest value.
+    } else { // NaN
+        return 0;
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_hfp64_truncate(f: u64) -> u64 {
+    let sign_bit = f & 1 << 63;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let b = if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_hfp(a.into(), -1074, 56, true)
+    } else if a < 2047 << 52 { // Normal
+        to_hfp((a & !0 >> 12 | 1 << 52).into(), (a >> 52) as i32 - 1075, 56, true)
+    } else if a == 2047 << 52 { // Infinity
+        !0 >> 1 // The largest value.
+    } else { // NaN
+        return 0;
+    };
+    b | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_hfp32(f: u32) -> u32 {
+    f64_to_hfp32(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_hfp32_truncate(f: u32) -> u32 {
+    f64_to_hfp32_truncate(f32_to_f64(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_hfp64(f: u32) -> u64 {
+    f64_to_hfp64(f32_to_f64(f)) // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_hfp32(x: u8) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 24, false) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_hfp32(i: i8) -> u32 {
+    let sign_bit = ((i >> 7) as u32) << 31;
+    u8_to_hfp32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_hfp32(x: u16) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 24, false) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_hfp32(i: i16) -> u32 {
+    let sign_bit = ((i >> 15) as u32) << 31;
+    u16_to_hfp32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_hfp32(x: u32) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 24, false) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_hfp32_truncate(x: u32) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 24, true) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_hfp32(i: i32) -> u32 {
+    let sign_bit = ((i >> 31) as u32) << 31;
+    u32_to_hfp32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_hfp32_truncate(i: i32) -> u32 {
+    let sign_bit = ((i >> 31) as u32) << 31;
+    u32_to_hfp32_truncate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_hfp32(x: u64) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 24, false) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_hfp32_truncate(x: u64) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 24, true) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_hfp32(i: i64) -> u32 {
+    let sign_bit = ((i >> 63) as u32) << 31;
+    u64_to_hfp32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_hfp32_truncate(i: i64) -> u32 {
+    let sign_bit = ((i >> 63) as u32) << 31;
+    u64_to_hfp32_truncate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_hfp32(x: u128) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x, 0, 24, false) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_hfp32_truncate(x: u128) -> u32 {
+    if x == 0 { return 0; }
+    to_hfp(x, 0, 24, true) as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_hfp32(i: i128) -> u32 {
+    let sign_bit = ((i >> 127) as u32) << 31;
+    u128_to_hfp32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_hfp32_truncate(i: i128) -> u32 {
+    let sign_bit = ((i >> 127) as u32) << 31;
+    u128_to_hfp32_truncate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_hfp64(x: u8) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 56, false)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_hfp64(i: i8) -> u64 {
+    let sign_bit = ((i >> 7) as u64) << 63;
+    u8_to_hfp64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_hfp64(x: u16) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 56, false)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_hfp64(i: i16) -> u64 {
+    let sign_bit = ((i >> 15) as u64) << 63;
+    u16_to_hfp64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_hfp64(x: u32) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 56, false)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_hfp64(i: i32) -> u64 {
+    let sign_bit = ((i >> 31) as u64) << 63;
+    u32_to_hfp64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_hfp64(x: u64) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 56, false)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_hfp64_truncate(x: u64) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x.into(), 0, 56, true)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_hfp64(i: i64) -> u64 {
+    let sign_bit = ((i >> 63) as u64) << 63;
+    u64_to_hfp64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_hfp64_truncate(i: i64) -> u64 {
+    let sign_bit = ((i >> 63) as u64) << 63;
+    u64_to_hfp64_truncate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_hfp64(x: u128) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x, 0, 56, false)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_hfp64_truncate(x: u128) -> u64 {
+    if x == 0 { return 0; }
+    to_hfp(x, 0, 56, true)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_hfp64(i: i128) -> u64 {
+    let sign_bit = ((i >> 127) as u64) << 63;
+    u128_to_hfp64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_hfp64_truncate(i: i128) -> u64 {
+    let sign_bit = ((i >> 127) as u64) << 63;
+    u128_to_hfp64_truncate(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_u8(h: u32) -> u8 {
+    if h >> 31 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24) as u64, 24).min(u8::MAX.into()) as u8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_u16(h: u32) -> u16 {
+    if h >> 31 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24) as u64, 24).min(u16::MAX.into()) as u16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_u32(h: u32) -> u32 {
+    if h >> 31 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24) as u64, 24).min(u32::MAX.into()) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_u64(h: u32) -> u64 {
+    if h >> 31 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24) as u64, 24).min(u64::MAX.into()) as u64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_u128(h: u32) -> u128 {
+    if h >> 31 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24) as u64, 24)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_i8(h: u32) -> i8 {
+    let a = hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24 & 0x7F) as u64, 24);
+    if h >> 31 != 0 { // Negative
+        (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg()
+    } else {
+        a.min(i8::MAX as u128) as i8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_i16(h: u32) -> i16 {
+    let a = hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24 & 0x7F) as u64, 24);
+    if h >> 31 != 0 { // Negative
+        (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg()
+    } else {
+        a.min(i16::MAX as u128) as i16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_i32(h: u32) -> i32 {
+    let a = hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24 & 0x7F) as u64, 24);
+    if h >> 31 != 0 { // Negative
+        (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg()
+    } else {
+        a.min(i32::MAX as u128) as i32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_i64(h: u32) -> i64 {
+    let a = hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24 & 0x7F) as u64, 24);
+    if h >> 31 != 0 { // Negative
+        (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg()
+    } else {
+        a.min(i64::MAX as u128) as i64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp32_to_i128(h: u32) -> i128 {
+    let a = hfp_to_u128((h & 0xFF_FFFF) as u64, (h >> 24 & 0x7F) as u64, 24);
+    if h >> 31 != 0 { // Negative
+        (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg()
+    } else {
+        a.min(i128::MAX as u128) as i128
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_u8(h: u64) -> u8 {
+    if h >> 63 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128(h & !0 >> 8, h >> 56, 56).min(u8::MAX.into()) as u8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_u16(h: u64) -> u16 {
+    if h >> 63 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128(h & !0 >> 8, h >> 56, 56).min(u16::MAX.into()) as u16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_u32(h: u64) -> u32 {
+    if h >> 63 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128(h & !0 >> 8, h >> 56, 56).min(u32::MAX.into()) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_u64(h: u64) -> u64 {
+    if h >> 63 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128(h & !0 >> 8, h >> 56, 56).min(u64::MAX.into()) as u64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_u128(h: u64) -> u128 {
+    if h >> 63 != 0 { // Negative
+        0
+    } else {
+        hfp_to_u128(h & !0 >> 8, h >> 56, 56)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_i8(h: u64) -> i8 {
+    let a = hfp_to_u128(h & !0 >> 8, h >> 56 & 0x7F, 56);
+    if h >> 63 != 0 { // Negative
+        (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg()
+    } else {
+        a.min(i8::MAX as u128) as i8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_i16(h: u64) -> i16 {
+    let a = hfp_to_u128(h & !0 >> 8, h >> 56 & 0x7F, 56);
+    if h >> 63 != 0 { // Negative
+        (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg()
+    } else {
+        a.min(i16::MAX as u128) as i16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_i32(h: u64) -> i32 {
+    let a = hfp_to_u128(h & !0 >> 8, h >> 56 & 0x7F, 56);
+    if h >> 63 != 0 { // Negative
+        (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg()
+    } else {
+        a.min(i32::MAX as u128) as i32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_i64(h: u64) -> i64 {
+    let a = hfp_to_u128(h & !0 >> 8, h >> 56 & 0x7F, 56);
+    if h >> 63 != 0 { // Negative
+        (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg()
+    } else {
+        a.min(i64::MAX as u128) as i64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn hfp64_to_i128(h: u64) -> i128 {
+    let a = hfp_to_u128(h & !0 >> 8, h >> 56 & 0x7F, 56);
+    if h >> 63 != 0 { // Negative
+        (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg()
+    } else {
+        a.min(i128::MAX as u128) as i128
+    }
+}
+
+/// Swaps the 16-bit words of a VAX F_floating number, converting between the
+/// order in memory and the logical order of the bits.
+#[cfg_attr(not(noinline), inline)]
+fn vax32_words(v: u32) -> u32 {
+    v.rotate_left(16)
+}
+
+/// Reverses the 16-bit words of a VAX D_floating or G_floating number,
+/// converting between the order in memory and the logical order of the bits.
+#[cfg_attr(not(noinline), inline)]
+fn vax64_words(v: u64) -> u64 {
+    let v = v.rotate_left(32);
+    (v & 0xFFFF_0000_FFFF) << 16 | v >> 16 & 0xFFFF_0000_FFFF
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_f32(v: u32) -> u32 {
+    let l = vax32_words(v);
+    if l >> 23 == 1 << 8 { // Reserved operand
+        0x7FC0_0000
+    } else {
+        minifloat::convert::<minifloat::VaxF, minifloat::F32>(l.into()) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_f64(v: u32) -> u64 {
+    let l = vax32_words(v);
+    if l >> 23 == 1 << 8 { // Reserved operand
+        0x7FF8 << 48
+    } else {
+        minifloat::convert::<minifloat::VaxF, minifloat::F64>(l.into())
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_vaxf(f: u32) -> u32 {
+    if f & !0 >> 1 > 255 << 23 { // NaN
+        vax32_words(1 << 31) // Reserved operand
+    } else {
+        vax32_words(minifloat::from_f32::<minifloat::VaxF>(f) as u32)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_vaxf(f: u64) -> u32 {
+    if f & !0 >> 1 > 2047 << 52 { // NaN
+        vax32_words(1 << 31) // Reserved operand
+    } else {
+        vax32_words(minifloat::from_f64::<minifloat::VaxF>(f) as u32)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_vaxf(x: u8) -> u32 {
+    vax32_words(minifloat::from_u8::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_vaxf(x: u16) -> u32 {
+    vax32_words(minifloat::from_u16::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_vaxf(x: u32) -> u32 {
+    vax32_words(minifloat::from_u32::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_vaxf(x: u64) -> u32 {
+    vax32_words(minifloat::from_u64::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_vaxf(x: u128) -> u32 {
+    vax32_words(minifloat::from_u128::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_vaxf(x: i8) -> u32 {
+    vax32_words(minifloat::from_i8::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_vaxf(x: i16) -> u32 {
+    vax32_words(minifloat::from_i16::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_vaxf(x: i32) -> u32 {
+    vax32_words(minifloat::from_i32::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_vaxf(x: i64) -> u32 {
+    vax32_words(minifloat::from_i64::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_vaxf(x: i128) -> u32 {
+    vax32_words(minifloat::from_i128::<minifloat::VaxF>(x) as u32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_u8(v: u32) -> u8 {
+    minifloat::to_u8::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_u16(v: u32) -> u16 {
+    minifloat::to_u16::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_u32(v: u32) -> u32 {
+    minifloat::to_u32::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_u64(v: u32) -> u64 {
+    minifloat::to_u64::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_u128(v: u32) -> u128 {
+    minifloat::to_u128::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_i8(v: u32) -> i8 {
+    minifloat::to_i8::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_i16(v: u32) -> i16 {
+    minifloat::to_i16::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_i32(v: u32) -> i32 {
+    minifloat::to_i32::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_i64(v: u32) -> i64 {
+    minifloat::to_i64::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxf_to_i128(v: u32) -> i128 {
+    minifloat::to_i128::<minifloat::VaxF>(vax32_words(v).into()) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_f32(v: u64) -> u32 {
+    let l = vax64_words(v);
+    if l >> 55 == 1 << 8 { // Reserved operand
+        0x7FC0_0000
+    } else {
+        minifloat::convert::<minifloat::VaxD, minifloat::F32>(l) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_f64(v: u64) -> u64 {
+    let l = vax64_words(v);
+    if l >> 55 == 1 << 8 { // Reserved operand
+        0x7FF8 << 48
+    } else {
+        minifloat::convert::<minifloat::VaxD, minifloat::F64>(l)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_vaxd(f: u32) -> u64 {
+    if f & !0 >> 1 > 255 << 23 { // NaN
+        vax64_words(1 << 63) // Reserved operand
+    } else {
+        vax64_words(minifloat::from_f32::<minifloat::VaxD>(f))
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_vaxd(f: u64) -> u64 {
+    if f & !0 >> 1 > 2047 << 52 { // NaN
+        vax64_words(1 << 63) // Reserved operand
+    } else {
+        vax64_words(minifloat::from_f64::<minifloat::VaxD>(f))
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_vaxd(x: u8) -> u64 {
+    vax64_words(minifloat::from_u8::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_vaxd(x: u16) -> u64 {
+    vax64_words(minifloat::from_u16::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_vaxd(x: u32) -> u64 {
+    vax64_words(minifloat::from_u32::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_vaxd(x: u64) -> u64 {
+    vax64_words(minifloat::from_u64::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_vaxd(x: u128) -> u64 {
+    vax64_words(minifloat::from_u128::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_vaxd(x: i8) -> u64 {
+    vax64_words(minifloat::from_i8::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_vaxd(x: i16) -> u64 {
+    vax64_words(minifloat::from_i16::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_vaxd(x: i32) -> u64 {
+    vax64_words(minifloat::from_i32::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_vaxd(x: i64) -> u64 {
+    vax64_words(minifloat::from_i64::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_vaxd(x: i128) -> u64 {
+    vax64_words(minifloat::from_i128::<minifloat::VaxD>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_u8(v: u64) -> u8 {
+    minifloat::to_u8::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_u16(v: u64) -> u16 {
+    minifloat::to_u16::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_u32(v: u64) -> u32 {
+    minifloat::to_u32::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_u64(v: u64) -> u64 {
+    minifloat::to_u64::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_u128(v: u64) -> u128 {
+    minifloat::to_u128::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_i8(v: u64) -> i8 {
+    minifloat::to_i8::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_i16(v: u64) -> i16 {
+    minifloat::to_i16::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_i32(v: u64) -> i32 {
+    minifloat::to_i32::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_i64(v: u64) -> i64 {
+    minifloat::to_i64::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxd_to_i128(v: u64) -> i128 {
+    minifloat::to_i128::<minifloat::VaxD>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_f32(v: u64) -> u32 {
+    let l = vax64_words(v);
+    if l >> 52 == 1 << 11 { // Reserved operand
+        0x7FC0_0000
+    } else {
+        minifloat::convert::<minifloat::VaxG, minifloat::F32>(l) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_f64(v: u64) -> u64 {
+    let l = vax64_words(v);
+    if l >> 52 == 1 << 11 { // Reserved operand
+        0x7FF8 << 48
+    } else {
+        minifloat::convert::<minifloat::VaxG, minifloat::F64>(l)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_vaxg(f: u32) -> u64 {
+    if f & !0 >> 1 > 255 << 23 { // NaN
+        vax64_words(1 << 63) // Reserved operand
+    } else {
+        vax64_words(minifloat::from_f32::<minifloat::VaxG>(f))
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_vaxg(f: u64) -> u64 {
+    if f & !0 >> 1 > 2047 << 52 { // NaN
+        vax64_words(1 << 63) // Reserved operand
+    } else {
+        vax64_words(minifloat::from_f64::<minifloat::VaxG>(f))
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_vaxg(x: u8) -> u64 {
+    vax64_words(minifloat::from_u8::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_vaxg(x: u16) -> u64 {
+    vax64_words(minifloat::from_u16::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_vaxg(x: u32) -> u64 {
+    vax64_words(minifloat::from_u32::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_vaxg(x: u64) -> u64 {
+    vax64_words(minifloat::from_u64::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_vaxg(x: u128) -> u64 {
+    vax64_words(minifloat::from_u128::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_vaxg(x: i8) -> u64 {
+    vax64_words(minifloat::from_i8::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_vaxg(x: i16) -> u64 {
+    vax64_words(minifloat::from_i16::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_vaxg(x: i32) -> u64 {
+    vax64_words(minifloat::from_i32::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_vaxg(x: i64) -> u64 {
+    vax64_words(minifloat::from_i64::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_vaxg(x: i128) -> u64 {
+    vax64_words(minifloat::from_i128::<minifloat::VaxG>(x))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_u8(v: u64) -> u8 {
+    minifloat::to_u8::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_u16(v: u64) -> u16 {
+    minifloat::to_u16::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_u32(v: u64) -> u32 {
+    minifloat::to_u32::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_u64(v: u64) -> u64 {
+    minifloat::to_u64::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_u128(v: u64) -> u128 {
+    minifloat::to_u128::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_i8(v: u64) -> i8 {
+    minifloat::to_i8::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_i16(v: u64) -> i16 {
+    minifloat::to_i16::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_i32(v: u64) -> i32 {
+    minifloat::to_i32::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_i64(v: u64) -> i64 {
+    minifloat::to_i64::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn vaxg_to_i128(v: u64) -> i128 {
+    minifloat::to_i128::<minifloat::VaxG>(vax64_words(v)) // Reserved operands are converted to zero, like NaN.
+}
+
+/// Converts m × 2^e (with m > 0) to the magnitude of an MBF number with a
+/// mantissa of `bits` bits, rounded to the closest value. Also returns
+/// whether it overflowed, in which case the result is the largest value.
+///
+/// Results that are too small result in zero.
+#[cfg_attr(not(noinline), inline)]
+fn to_mbf(m: u128, e: i32, bits: u32) -> (u64, bool) {
+    let max = 255 << (bits + 1) | !(!0 << bits); // All bits set, except the sign bit.
+    let n = 127 - m.leading_zeros() as i32; // Position of the highest 1-bit.
+    let x = n + e + 129; // Exponent plus 129, if it is in range.
+    if x > 255 { return (max, true); }
+    let s = n - bits as i32 + (1 - x).max(0); // Number of insignificant bits.
+    let r = if s <= 0 {
+        (m << -s) as u64 // Exact.
+    } else if s > n + 1 {
+        0 // Less than half of the smallest step.
+    } else {
+        let a = m >> s; // Significant bits.
+        let b = m << (128 - s); // Insignificant bits, only relevant for rounding.
+        (a + ((b - (b >> 127 & !a)) >> 127)) as u64 // Add one when we need to round up. Break ties to even.
+    };
+    let a = (((x.max(1) - 1) as u64) << bits) + r; // + not |, so the mantissa can overflow into the exponent.
+    if a >> bits == 0 { // Too small
+        (0, false)
+    } else if a >> bits > 255 { // Rounding overflowed
+        (max, true)
+    } else {
+        ((a >> bits) << (bits + 1) | a & !(!0 << bits), false) // Leave room for the sign bit.
+    }
+}
+
+/// Converts a non-zero MBF number with a mantissa of `bits` bits to the bits
+/// of the magnitude of an f64, rounded towards zero. Also returns the
+/// insignificant bits, only relevant for rounding.
+#[cfg_attr(not(noinline), inline)]
+fn mbf_to_f64_truncated(x: u64, bits: u32) -> (u64, u64) {
+    let e = (x >> (bits + 1) & 0xFF) + 894; // Exponent plus 1023. Always in the normal range.
+    let m = x & !(!0 << bits); // Mantissa, without the implicit 1-bit.
+    if bits <= 52 {
+        (e << 52 | m << (52 - bits), 0)
+    } else {
+        (e << 52 | m >> (bits - 52), m << (116 - bits))
+    }
+}
+
+/// Truncates an MBF number with a mantissa of `bits` bits to an integer,
+/// ignoring the sign. The result always fits.
+#[cfg_attr(not(noinline), inline)]
+fn mbf_to_u128(x: u64, bits: u32) -> u128 {
+    let e = (x >> (bits + 1) & 0xFF) as i32;
+    if e == 0 { return 0; }
+    let m = (x & !(!0 << bits) | 1 << bits) as u128; // Mantissa, with the implicit 1-bit.
+    let k = e - 129 - bits as i32; // Exponent of the lowest bit of the mantissa.
+    if k < 0 {
+        m.checked_shr(-k as u32).unwrap_or(0)
+    } else {
+        m << k
+    }
+}
+
+/// Converts an f64 to an MBF number with a mantissa of `bits` bits. Also
+/// returns whether it did not fit, which includes infinity and NaN.
+#[cfg_attr(not(noinline), inline)]
+fn f64_to_mbf_rounded(f: u64, bits: u32) -> (u64, bool) {
+    let sign_bit = (f >> 63) << bits;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let (b, overflow) = if a < 1 << 52 { // Zero or subnormal
+        (0, false) // Far too small.
+    } else if a < 2047 << 52 { // Normal
+        to_mbf((a & !0 >> 12 | 1 << 52).into(), (a >> 52) as i32 - 1075, bits)
+    } else if a == 2047 << 52 { // Infinity
+        (to_mbf(1, 1000, bits).0, true) // The largest value.
+    } else { // NaN
+        return (0, true);
+    };
+    if b == 0 { (0, overflow) } else { (b | sign_bit, overflow) }
+}
+
+/// Converts an MBF number with a mantissa of `from` bits to one with a
+/// mantissa of `to` bits. Also returns whether it overflowed.
+#[cfg_attr(not(noinline), inline)]
+fn mbf_to_mbf(x: u64, from: u32, to: u32) -> (u64, bool) {
+    let e = x >> (from + 1) & 0xFF;
+    if e == 0 { return (0, false); }
+    let sign_bit = (x >> from & 1) << to;
+    let m = x & !(!0 << from) | 1 << from; // Mantissa, with the implicit 1-bit.
+    let (a, overflow) = to_mbf(m.into(), e as i32 - 129 - from as i32, to);
+    (a | sign_bit, overflow)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_f64(x: u32) -> u64 {
+    let sign_bit = ((x >> 23 & 1) as u64) << 63;
+    if x >> 24 == 0 { return 0; }
+    mbf_to_f64_truncated(x.into(), 23).0 | sign_bit // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_f32(x: u32) -> u32 {
+    f64_to_f32(mbf32_to_f64(x)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_f64(x: u64) -> u64 {
+    let sign_bit = (x >> 31 & 1) << 63;
+    if x >> 32 == 0 { return 0; }
+    mbf_to_f64_truncated(x, 31).0 | sign_bit // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_f32(x: u64) -> u32 {
+    f64_to_f32(mbf40_to_f64(x)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_f64(x: u64) -> u64 {
+    let sign_bit = (x >> 55 & 1) << 63;
+    if x >> 56 == 0 { return 0; }
+    let (a, b) = mbf_to_f64_truncated(x, 55);
+    (a + ((b - (b >> 63 & !a)) >> 63)) | sign_bit // Add one when we need to round up. Break ties to even.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_f32(x: u64) -> u32 {
+    let sign_bit = (x >> 55 & 1) << 63;
+    if x >> 56 == 0 { return 0; }
+    let (a, b) = mbf_to_f64_truncated(x, 55);
+    f64_to_f32(a | (b != 0) as u64 | sign_bit) // Round to odd first, to avoid rounding twice.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_mbf32(f: u64) -> u32 {
+    f64_to_mbf_rounded(f, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_mbf32_checked(f: u64) -> Option<u32> {
+    match f64_to_mbf_rounded(f, 23) {
+        (x, false) => Some(x as u32),
+        (_, true) => None,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_mbf32(f: u32) -> u32 {
+    f64_to_mbf32(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_mbf32_checked(f: u32) -> Option<u32> {
+    f64_to_mbf32_checked(f32_to_f64(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_mbf40(f: u64) -> u64 {
+    f64_to_mbf_rounded(f, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_mbf40_checked(f: u64) -> Option<u64> {
+    match f64_to_mbf_rounded(f, 31) {
+        (x, false) => Some(x),
+        (_, true) => None,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_mbf40(f: u32) -> u64 {
+    f64_to_mbf40(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_mbf40_checked(f: u32) -> Option<u64> {
+    f64_to_mbf40_checked(f32_to_f64(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_mbf64(f: u64) -> u64 {
+    f64_to_mbf_rounded(f, 55).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_mbf64_checked(f: u64) -> Option<u64> {
+    match f64_to_mbf_rounded(f, 55) {
+        (x, false) => Some(x),
+        (_, true) => None,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_mbf64(f: u32) -> u64 {
+    f64_to_mbf64(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_mbf64_checked(f: u32) -> Option<u64> {
+    f64_to_mbf64_checked(f32_to_f64(f))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_mbf40(x: u32) -> u64 {
+    mbf_to_mbf(x.into(), 23, 31).0 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_mbf64(x: u32) -> u64 {
+    mbf_to_mbf(x.into(), 23, 55).0 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_mbf32(x: u64) -> u32 {
+    mbf_to_mbf(x, 31, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_mbf32_checked(x: u64) -> Option<u32> {
+    match mbf_to_mbf(x, 31, 23) {
+        (x, false) => Some(x as u32),
+        (_, true) => None,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_mbf64(x: u64) -> u64 {
+    mbf_to_mbf(x, 31, 55).0 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_mbf32(x: u64) -> u32 {
+    mbf_to_mbf(x, 55, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_mbf32_checked(x: u64) -> Option<u32> {
+    match mbf_to_mbf(x, 55, 23) {
+        (x, false) => Some(x as u32),
+        (_, true) => None,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_mbf40(x: u64) -> u64 {
+    mbf_to_mbf(x, 55, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_mbf40_checked(x: u64) -> Option<u64> {
+    match mbf_to_mbf(x, 55, 31) {
+        (x, false) => Some(x),
+        (_, true) => None,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_mbf32(x: u8) -> u32 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_mbf32(i: i8) -> u32 {
+    let sign_bit = ((i < 0) as u32) << 23;
+    u8_to_mbf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_mbf32(x: u16) -> u32 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_mbf32(i: i16) -> u32 {
+    let sign_bit = ((i < 0) as u32) << 23;
+    u16_to_mbf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_mbf32(x: u32) -> u32 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_mbf32(i: i32) -> u32 {
+    let sign_bit = ((i < 0) as u32) << 23;
+    u32_to_mbf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_mbf32(x: u64) -> u32 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_mbf32(i: i64) -> u32 {
+    let sign_bit = ((i < 0) as u32) << 23;
+    u64_to_mbf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_mbf32(x: u128) -> u32 {
+    if x == 0 { return 0; }
+    to_mbf(x, 0, 23).0 as u32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_mbf32(i: i128) -> u32 {
+    let sign_bit = ((i < 0) as u32) << 23;
+    u128_to_mbf32(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_mbf40(x: u8) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_mbf40(i: i8) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 31;
+    u8_to_mbf40(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_mbf40(x: u16) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_mbf40(i: i16) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 31;
+    u16_to_mbf40(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_mbf40(x: u32) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_mbf40(i: i32) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 31;
+    u32_to_mbf40(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_mbf40(x: u64) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_mbf40(i: i64) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 31;
+    u64_to_mbf40(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_mbf40(x: u128) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x, 0, 31).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_mbf40(i: i128) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 31;
+    u128_to_mbf40(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_mbf64(x: u8) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 55).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_mbf64(i: i8) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 55;
+    u8_to_mbf64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_mbf64(x: u16) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 55).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_mbf64(i: i16) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 55;
+    u16_to_mbf64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_mbf64(x: u32) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 55).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_mbf64(i: i32) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 55;
+    u32_to_mbf64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_mbf64(x: u64) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x.into(), 0, 55).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_mbf64(i: i64) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 55;
+    u64_to_mbf64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_mbf64(x: u128) -> u64 {
+    if x == 0 { return 0; }
+    to_mbf(x, 0, 55).0
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_mbf64(i: i128) -> u64 {
+    let sign_bit = ((i < 0) as u64) << 55;
+    u128_to_mbf64(i.unsigned_abs()) | sign_bit
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_u8(x: u32) -> u8 {
+    if x >> 23 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x.into(), 23).min(u8::MAX.into()) as u8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_u16(x: u32) -> u16 {
+    if x >> 23 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x.into(), 23).min(u16::MAX.into()) as u16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_u32(x: u32) -> u32 {
+    if x >> 23 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x.into(), 23).min(u32::MAX.into()) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_u64(x: u32) -> u64 {
+    if x >> 23 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x.into(), 23).min(u64::MAX.into()) as u64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_u128(x: u32) -> u128 {
+    if x >> 23 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x.into(), 23)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_i8(x: u32) -> i8 {
+    let a = mbf_to_u128(x.into(), 23);
+    if x >> 23 & 1 != 0 { // Negative
+        (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg()
+    } else {
+        a.min(i8::MAX as u128) as i8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_i16(x: u32) -> i16 {
+    let a = mbf_to_u128(x.into(), 23);
+    if x >> 23 & 1 != 0 { // Negative
+        (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg()
+    } else {
+        a.min(i16::MAX as u128) as i16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_i32(x: u32) -> i32 {
+    let a = mbf_to_u128(x.into(), 23);
+    if x >> 23 & 1 != 0 { // Negative
+        (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg()
+    } else {
+        a.min(i32::MAX as u128) as i32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_i64(x: u32) -> i64 {
+    let a = mbf_to_u128(x.into(), 23);
+    if x >> 23 & 1 != 0 { // Negative
+        (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg()
+    } else {
+        a.min(i64::MAX as u128) as i64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf32_to_i128(x: u32) -> i128 {
+    let a = mbf_to_u128(x.into(), 23);
+    if x >> 23 & 1 != 0 { // Negative
+        (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg()
+    } else {
+        a.min(i128::MAX as u128) as i128
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_u8(x: u64) -> u8 {
+    if x >> 31 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 31).min(u8::MAX.into()) as u8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_u16(x: u64) -> u16 {
+    if x >> 31 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 31).min(u16::MAX.into()) as u16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_u32(x: u64) -> u32 {
+    if x >> 31 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 31).min(u32::MAX.into()) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_u64(x: u64) -> u64 {
+    if x >> 31 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 31).min(u64::MAX.into()) as u64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_u128(x: u64) -> u128 {
+    if x >> 31 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 31)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_i8(x: u64) -> i8 {
+    let a = mbf_to_u128(x, 31);
+    if x >> 31 & 1 != 0 { // Negative
+        (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg()
+    } else {
+        a.min(i8::MAX as u128) as i8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_i16(x: u64) -> i16 {
+    let a = mbf_to_u128(x, 31);
+    if x >> 31 & 1 != 0 { // Negative
+        (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg()
+    } else {
+        a.min(i16::MAX as u128) as i16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_i32(x: u64) -> i32 {
+    let a = mbf_to_u128(x, 31);
+    if x >> 31 & 1 != 0 { // Negative
+        (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg()
+    } else {
+        a.min(i32::MAX as u128) as i32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_i64(x: u64) -> i64 {
+    let a = mbf_to_u128(x, 31);
+    if x >> 31 & 1 != 0 { // Negative
+        (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg()
+    } else {
+        a.min(i64::MAX as u128) as i64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf40_to_i128(x: u64) -> i128 {
+    let a = mbf_to_u128(x, 31);
+    if x >> 31 & 1 != 0 { // Negative
+        (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg()
+    } else {
+        a.min(i128::MAX as u128) as i128
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_u8(x: u64) -> u8 {
+    if x >> 55 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 55).min(u8::MAX.into()) as u8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_u16(x: u64) -> u16 {
+    if x >> 55 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 55).min(u16::MAX.into()) as u16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_u32(x: u64) -> u32 {
+    if x >> 55 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 55).min(u32::MAX.into()) as u32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_u64(x: u64) -> u64 {
+    if x >> 55 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 55).min(u64::MAX.into()) as u64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_u128(x: u64) -> u128 {
+    if x >> 55 & 1 != 0 { // Negative
+        0
+    } else {
+        mbf_to_u128(x, 55)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_i8(x: u64) -> i8 {
+    let a = mbf_to_u128(x, 55);
+    if x >> 55 & 1 != 0 { // Negative
+        (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg()
+    } else {
+        a.min(i8::MAX as u128) as i8
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_i16(x: u64) -> i16 {
+    let a = mbf_to_u128(x, 55);
+    if x >> 55 & 1 != 0 { // Negative
+        (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg()
+    } else {
+        a.min(i16::MAX as u128) as i16
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_i32(x: u64) -> i32 {
+    let a = mbf_to_u128(x, 55);
+    if x >> 55 & 1 != 0 { // Negative
+        (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg()
+    } else {
+        a.min(i32::MAX as u128) as i32
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_i64(x: u64) -> i64 {
+    let a = mbf_to_u128(x, 55);
+    if x >> 55 & 1 != 0 { // Negative
+        (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg()
+    } else {
+        a.min(i64::MAX as u128) as i64
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn mbf64_to_i128(x: u64) -> i128 {
+    let a = mbf_to_u128(x, 55);
+    if x >> 55 & 1 != 0 { // Negative
+        (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg()
+    } else {
+        a.min(i128::MAX as u128) as i128
+    }
+}
+
+/// Decodes a posit of `n` bits (with es = 2) that is not zero or NaR into its
+/// sign, its significand with the implicit 1-bit as bit 31, and its scale,
+/// such that its magnitude is m × 2^(scale - 31).
+#[cfg_attr(not(noinline), inline)]
+fn posit_decode(p: u32, n: u32) -> (bool, u32, i32) {
+    let x = p << (32 - n);
+    let negative = x >> 31 != 0;
+    let y = if negative { x.wrapping_neg() } else { x } << 1; // Remove sign bit.
+    let (r, k) = if y >> 31 != 0 { // Regime of ones
+        (y.leading_ones(), y.leading_ones() as i32 - 1)
+    } else { // Regime of zeros
+        (y.leading_zeros(), -(y.leading_zeros() as i32))
+    };
+    let z = y.checked_shl(r + 1).unwrap_or(0); // Exponent and fraction bits, after the regime and its terminating bit.
+    (negative, 1 << 31 | z << 2 >> 1, 4 * k + (z >> 30) as i32)
+}
+
+/// Rounds ±m × 2^e (with m > 0), plus a tiny non-zero amount if `sticky` is
+/// set, to a posit of `n` bits (with es = 2).
+///
+/// Values beyond the largest or smallest posit result in the largest or
+/// smallest posit, never in NaR or zero.
+#[cfg_attr(not(noinline), inline)]
+fn to_posit(negative: bool, m: u64, e: i32, sticky: bool, n: u32) -> u32 {
+    let l = 63 - m.leading_zeros(); // Position of the highest 1-bit.
+    let scale = l as i32 + e;
+    let k = scale >> 2; // Regime.
+    let max = (1 << (n - 1)) - 1; // maxpos
+    let p = if k >= n as i32 - 2 {
+        max
+    } else if k < 2 - n as i32 {
+        1 // minpos
+    } else {
+        // The regime, exponent and fraction bits, without limiting their length.
+        let (regime, regime_bits) = if k >= 0 { (((2u64 << k) - 1) << 1, k as u32 + 2) } else { (1, (-k) as u32 + 1) };
+        let x = (regime as u128) << (2 + l) | ((scale & 3) as u128) << l | (m & !(1 << l)) as u128;
+        let s = (regime_bits + 2 + l) as i32 - (n as i32 - 1); // Number of insignificant bits.
+        let p = if s <= 0 {
+            (x << -s) as u32 // Exact, apart from the sticky bit.
+        } else {
+            let a = (x >> s) as u32; // Significant bits.
+            let b = x << (128 - s) | sticky as u128; // Insignificant bits, only relevant for rounding.
+            a + ((b - (b >> 127 & !a as u128)) >> 127) as u32 // Add one when we need to round up. Break ties to even.
+        };
+        p.min(max)
+    };
+    if negative { p.wrapping_neg() & (!0 >> (32 - n)) } else { p }
+}
+
+/// Truncates a posit of `n` bits to an integer, and returns its sign and magnitude.
+#[cfg_attr(not(noinline), inline)]
+fn posit_to_u128(p: u32, n: u32) -> (bool, u128) {
+    if p << (33 - n) == 0 { return (false, 0); } // Zero or NaR
+    let (negative, m, scale) = posit_decode(p, n);
+    let s = scale - 31;
+    let a = if s < 0 { (m as u128).checked_shr(-s as u32).unwrap_or(0) } else { (m as u128) << s };
+    (negative, a)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_f64(p: u8) -> u64 {
+    if p == 0 { return 0; }
+    if p == 1 << 7 { return 0x7FF8 << 48; } // NaR
+    let (negative, m, scale) = posit_decode(p.into(), 8);
+    ((((scale + 1022) as u64) << 52) + ((m as u64) << 21)) | (negative as u64) << 63 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_f32(p: u8) -> u32 {
+    if p == 0 { return 0; }
+    if p == 1 << 7 { return 0x7FC0_0000; } // NaR
+    let (negative, m, scale) = posit_decode(p.into(), 8);
+    ((((scale + 126) as u32) << 23) + (m >> 8)) | (negative as u32) << 31 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_f64(p: u16) -> u64 {
+    if p == 0 { return 0; }
+    if p == 1 << 15 { return 0x7FF8 << 48; } // NaR
+    let (negative, m, scale) = posit_decode(p.into(), 16);
+    ((((scale + 1022) as u64) << 52) + ((m as u64) << 21)) | (negative as u64) << 63 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_f32(p: u16) -> u32 {
+    if p == 0 { return 0; }
+    if p == 1 << 15 { return 0x7FC0_0000; } // NaR
+    let (negative, m, scale) = posit_decode(p.into(), 16);
+    ((((scale + 126) as u32) << 23) + (m >> 8)) | (negative as u32) << 31 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_f64(p: u32) -> u64 {
+    if p == 0 { return 0; }
+    if p == 1 << 31 { return 0x7FF8 << 48; } // NaR
+    let (negative, m, scale) = posit_decode(p, 32);
+    ((((scale + 1022) as u64) << 52) + ((m as u64) << 21)) | (negative as u64) << 63 // Always exact.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_f32(p: u32) -> u32 {
+    f64_to_f32(posit32_to_f64(p)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_posit8(f: u64) -> u8 {
+    let negative = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_posit(negative, a, -1074, false, 8) as u8
+    } else if a < 2047 << 52 { // Normal
+        to_posit(negative, a & !0 >> 12 | 1 << 52, (a >> 52) as i32 - 1075, false, 8) as u8
+    } else { // Infinity or NaN
+        1 << 7 // NaR
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_posit8(f: u32) -> u8 {
+    f64_to_posit8(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_posit16(f: u64) -> u16 {
+    let negative = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_posit(negative, a, -1074, false, 16) as u16
+    } else if a < 2047 << 52 { // Normal
+        to_posit(negative, a & !0 >> 12 | 1 << 52, (a >> 52) as i32 - 1075, false, 16) as u16
+    } else { // Infinity or NaN
+        1 << 15 // NaR
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_posit16(f: u32) -> u16 {
+    f64_to_posit16(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_posit32(f: u64) -> u32 {
+    let negative = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    if a == 0 { // Zero
+        0
+    } else if a < 1 << 52 { // Subnormal
+        to_posit(negative, a, -1074, false, 32)
+    } else if a < 2047 << 52 { // Normal
+        to_posit(negative, a & !0 >> 12 | 1 << 52, (a >> 52) as i32 - 1075, false, 32)
+    } else { // Infinity or NaN
+        1 << 31 // NaR
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_posit32(f: u32) -> u32 {
+    f64_to_posit32(f32_to_f64(f)) // Lossless conversion to f64, so only rounds once.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_posit8(x: u8) -> u8 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 8) as u8
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_posit8(x: u16) -> u8 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 8) as u8
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_posit8(x: u32) -> u8 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 8) as u8
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_posit8(x: u64) -> u8 {
+    if x == 0 { return 0; }
+    to_posit(false, x, 0, false, 8) as u8
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_posit8(x: u128) -> u8 {
+    if x == 0 { return 0; }
+    let s = 64u32.saturating_sub(x.leading_zeros()); // Number of bits that don't fit in a u64.
+    to_posit(false, (x >> s) as u64, s as i32, x & !(!0 << s) != 0, 8) as u8
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_posit8(i: i8) -> u8 {
+    let p = u8_to_posit8(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_posit8(i: i16) -> u8 {
+    let p = u16_to_posit8(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_posit8(i: i32) -> u8 {
+    let p = u32_to_posit8(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_posit8(i: i64) -> u8 {
+    let p = u64_to_posit8(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_posit8(i: i128) -> u8 {
+    let p = u128_to_posit8(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_posit16(x: u8) -> u16 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 16) as u16
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_posit16(x: u16) -> u16 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 16) as u16
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_posit16(x: u32) -> u16 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 16) as u16
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_posit16(x: u64) -> u16 {
+    if x == 0 { return 0; }
+    to_posit(false, x, 0, false, 16) as u16
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_posit16(x: u128) -> u16 {
+    if x == 0 { return 0; }
+    let s = 64u32.saturating_sub(x.leading_zeros()); // Number of bits that don't fit in a u64.
+    to_posit(false, (x >> s) as u64, s as i32, x & !(!0 << s) != 0, 16) as u16
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_posit16(i: i8) -> u16 {
+    let p = u8_to_posit16(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_posit16(i: i16) -> u16 {
+    let p = u16_to_posit16(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_posit16(i: i32) -> u16 {
+    let p = u32_to_posit16(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_posit16(i: i64) -> u16 {
+    let p = u64_to_posit16(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_posit16(i: i128) -> u16 {
+    let p = u128_to_posit16(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_posit32(x: u8) -> u32 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_posit32(x: u16) -> u32 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_posit32(x: u32) -> u32 {
+    if x == 0 { return 0; }
+    to_posit(false, x.into(), 0, false, 32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_posit32(x: u64) -> u32 {
+    if x == 0 { return 0; }
+    to_posit(false, x, 0, false, 32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_posit32(x: u128) -> u32 {
+    if x == 0 { return 0; }
+    let s = 64u32.saturating_sub(x.leading_zeros()); // Number of bits that don't fit in a u64.
+    to_posit(false, (x >> s) as u64, s as i32, x & !(!0 << s) != 0, 32)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_posit32(i: i8) -> u32 {
+    let p = u8_to_posit32(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_posit32(i: i16) -> u32 {
+    let p = u16_to_posit32(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_posit32(i: i32) -> u32 {
+    let p = u32_to_posit32(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_posit32(i: i64) -> u32 {
+    let p = u64_to_posit32(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_posit32(i: i128) -> u32 {
+    let p = u128_to_posit32(i.unsigned_abs());
+    if i < 0 { p.wrapping_neg() } else { p }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_u8(p: u8) -> u8 {
+    match posit_to_u128(p.into(), 8) {
+        (true, _) => 0,
+        (false, a) => a.min(u8::MAX.into()) as u8,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_u16(p: u8) -> u16 {
+    match posit_to_u128(p.into(), 8) {
+        (true, _) => 0,
+        (false, a) => a.min(u16::MAX.into()) as u16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_u32(p: u8) -> u32 {
+    match posit_to_u128(p.into(), 8) {
+        (true, _) => 0,
+        (false, a) => a.min(u32::MAX.into()) as u32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_u64(p: u8) -> u64 {
+    match posit_to_u128(p.into(), 8) {
+        (true, _) => 0,
+        (false, a) => a.min(u64::MAX.into()) as u64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_u128(p: u8) -> u128 {
+    match posit_to_u128(p.into(), 8) {
+        (true, _) => 0,
+        (false, a) => a,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_i8(p: u8) -> i8 {
+    match posit_to_u128(p.into(), 8) {
+        (true, a) => (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg(),
+        (false, a) => a.min(i8::MAX as u128) as i8,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_i16(p: u8) -> i16 {
+    match posit_to_u128(p.into(), 8) {
+        (true, a) => (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg(),
+        (false, a) => a.min(i16::MAX as u128) as i16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_i32(p: u8) -> i32 {
+    match posit_to_u128(p.into(), 8) {
+        (true, a) => (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg(),
+        (false, a) => a.min(i32::MAX as u128) as i32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_i64(p: u8) -> i64 {
+    match posit_to_u128(p.into(), 8) {
+        (true, a) => (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg(),
+        (false, a) => a.min(i64::MAX as u128) as i64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit8_to_i128(p: u8) -> i128 {
+    match posit_to_u128(p.into(), 8) {
+        (true, a) => (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg(),
+        (false, a) => a.min(i128::MAX as u128) as i128,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_u8(p: u16) -> u8 {
+    match posit_to_u128(p.into(), 16) {
+        (true, _) => 0,
+        (false, a) => a.min(u8::MAX.into()) as u8,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_u16(p: u16) -> u16 {
+    match posit_to_u128(p.into(), 16) {
+        (true, _) => 0,
+        (false, a) => a.min(u16::MAX.into()) as u16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_u32(p: u16) -> u32 {
+    match posit_to_u128(p.into(), 16) {
+        (true, _) => 0,
+        (false, a) => a.min(u32::MAX.into()) as u32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_u64(p: u16) -> u64 {
+    match posit_to_u128(p.into(), 16) {
+        (true, _) => 0,
+        (false, a) => a.min(u64::MAX.into()) as u64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_u128(p: u16) -> u128 {
+    match posit_to_u128(p.into(), 16) {
+        (true, _) => 0,
+        (false, a) => a,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_i8(p: u16) -> i8 {
+    match posit_to_u128(p.into(), 16) {
+        (true, a) => (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg(),
+        (false, a) => a.min(i8::MAX as u128) as i8,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_i16(p: u16) -> i16 {
+    match posit_to_u128(p.into(), 16) {
+        (true, a) => (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg(),
+        (false, a) => a.min(i16::MAX as u128) as i16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_i32(p: u16) -> i32 {
+    match posit_to_u128(p.into(), 16) {
+        (true, a) => (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg(),
+        (false, a) => a.min(i32::MAX as u128) as i32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_i64(p: u16) -> i64 {
+    match posit_to_u128(p.into(), 16) {
+        (true, a) => (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg(),
+        (false, a) => a.min(i64::MAX as u128) as i64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit16_to_i128(p: u16) -> i128 {
+    match posit_to_u128(p.into(), 16) {
+        (true, a) => (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg(),
+        (false, a) => a.min(i128::MAX as u128) as i128,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_u8(p: u32) -> u8 {
+    match posit_to_u128(p, 32) {
+        (true, _) => 0,
+        (false, a) => a.min(u8::MAX.into()) as u8,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_u16(p: u32) -> u16 {
+    match posit_to_u128(p, 32) {
+        (true, _) => 0,
+        (false, a) => a.min(u16::MAX.into()) as u16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_u32(p: u32) -> u32 {
+    match posit_to_u128(p, 32) {
+        (true, _) => 0,
+        (false, a) => a.min(u32::MAX.into()) as u32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_u64(p: u32) -> u64 {
+    match posit_to_u128(p, 32) {
+        (true, _) => 0,
+        (false, a) => a.min(u64::MAX.into()) as u64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_u128(p: u32) -> u128 {
+    match posit_to_u128(p, 32) {
+        (true, _) => 0,
+        (false, a) => a,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_i8(p: u32) -> i8 {
+    match posit_to_u128(p, 32) {
+        (true, a) => (a.min(i8::MIN.unsigned_abs().into()) as i8).wrapping_neg(),
+        (false, a) => a.min(i8::MAX as u128) as i8,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_i16(p: u32) -> i16 {
+    match posit_to_u128(p, 32) {
+        (true, a) => (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg(),
+        (false, a) => a.min(i16::MAX as u128) as i16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_i32(p: u32) -> i32 {
+    match posit_to_u128(p, 32) {
+        (true, a) => (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg(),
+        (false, a) => a.min(i32::MAX as u128) as i32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_i64(p: u32) -> i64 {
+    match posit_to_u128(p, 32) {
+        (true, a) => (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg(),
+        (false, a) => a.min(i64::MAX as u128) as i64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn posit32_to_i128(p: u32) -> i128 {
+    match posit_to_u128(p, 32) {
+        (true, a) => (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg(),
+        (false, a) => a.min(i128::MAX as u128) as i128,
+    }
+}
+
+/// Rounds x × 2^e to the closest f64 (breaking ties to even), which might be
+/// subnormal, zero or infinity.
+#[cfg_attr(not(noinline), inline)]
+fn u128_scaled_to_f64(x: u128, e: i32) -> u64 {
+    if x == 0 { return 0; }
+    let e = e.clamp(-2048, 2048); // Larger values make no difference.
+    let n = x.leading_zeros();
+    let y = x << n;
+    let exp = 127 + e - n as i32; // Exponent of the highest 1-bit.
+    if exp > 1023 { return 0x7FF << 52; } // Overflow
+    let s = 75 + (-1022 - exp).max(0) as u32; // Number of insignificant bits, more for subnormals.
+    if s > 128 { return 0; } // Below half of the smallest subnormal.
+    let a = y.checked_shr(s).unwrap_or(0) as u64; // Significant bits, with bit 53 still in tact for normal numbers.
+    let b = y << (128 - s); // Insignificant bits, only relevant for rounding.
+    let b = (b >> 64) as u64 | (b as u64 != 0) as u64;
+    let m = a + ((b - (b >> 63 & !a)) >> 63); // Add one when we need to round up. Break ties to even.
+    let e = (exp + 1022).max(0) as u64; // Exponent plus 1023, minus one, or zero for subnormals.
+    (e << 52) + m // + not |, so the mantissa can overflow into the exponent.
+}
+
+/// Rounds x × 2^e to the closest f32 (breaking ties to even), which might be
+/// subnormal, zero or infinity.
+#[cfg_attr(not(noinline), inline)]
+fn u128_scaled_to_f32(x: u128, e: i32) -> u32 {
+    if x == 0 { return 0; }
+    let e = e.clamp(-2048, 2048); // Larger values make no difference.
+    let n = x.leading_zeros();
+    let y = x << n;
+    let exp = 127 + e - n as i32; // Exponent of the highest 1-bit.
+    if exp > 127 { return 0xFF << 23; } // Overflow
+    let s = 104 + (-126 - exp).max(0) as u32; // Number of insignificant bits, more for subnormals.
+    if s > 128 { return 0; } // Below half of the smallest subnormal.
+    let a = y.checked_shr(s).unwrap_or(0) as u32; // Significant bits, with bit 24 still in tact for normal numbers.
+    let b = y << (128 - s); // Insignificant bits, only relevant for rounding.
+    let b = (b >> 96) as u32 | (b << 32 != 0) as u32;
+    let m = a + ((b - (b >> 31 & !a)) >> 31); // Add one when we need to round up. Break ties to even.
+    let e = (exp + 126).max(0) as u32; // Exponent plus 127, minus one, or zero for subnormals.
+    (e << 23) + m // + not |, so the mantissa can overflow into the exponent.
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f32_scaled(x: u8, exp: i32) -> u32 {
+    u128_scaled_to_f32(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f32_scaled(x: u16, exp: i32) -> u32 {
+    u128_scaled_to_f32(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f32_scaled(x: u32, exp: i32) -> u32 {
+    u128_scaled_to_f32(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f32_scaled(x: u64, exp: i32) -> u32 {
+    u128_scaled_to_f32(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f32_scaled(x: u128, exp: i32) -> u32 {
+    u128_scaled_to_f32(x, exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f32_scaled(i: i8, exp: i32) -> u32 {
+    u8_to_f32_scaled(i.unsigned_abs(), exp) | ((i < 0) as u32) << 31
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f32_scaled(i: i16, exp: i32) -> u32 {
+    u16_to_f32_scaled(i.unsigned_abs(), exp) | ((i < 0) as u32) << 31
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f32_scaled(i: i32, exp: i32) -> u32 {
+    u32_to_f32_scaled(i.unsigned_abs(), exp) | ((i < 0) as u32) << 31
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f32_scaled(i: i64, exp: i32) -> u32 {
+    u64_to_f32_scaled(i.unsigned_abs(), exp) | ((i < 0) as u32) << 31
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f32_scaled(i: i128, exp: i32) -> u32 {
+    u128_to_f32_scaled(i.unsigned_abs(), exp) | ((i < 0) as u32) << 31
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u8_to_f64_scaled(x: u8, exp: i32) -> u64 {
+    u128_scaled_to_f64(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_to_f64_scaled(x: u16, exp: i32) -> u64 {
+    u128_scaled_to_f64(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_to_f64_scaled(x: u32, exp: i32) -> u64 {
+    u128_scaled_to_f64(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_to_f64_scaled(x: u64, exp: i32) -> u64 {
+    u128_scaled_to_f64(x.into(), exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_to_f64_scaled(x: u128, exp: i32) -> u64 {
+    u128_scaled_to_f64(x, exp)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i8_to_f64_scaled(i: i8, exp: i32) -> u64 {
+    u8_to_f64_scaled(i.unsigned_abs(), exp) | ((i < 0) as u64) << 63
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_to_f64_scaled(i: i16, exp: i32) -> u64 {
+    u16_to_f64_scaled(i.unsigned_abs(), exp) | ((i < 0) as u64) << 63
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_to_f64_scaled(i: i32, exp: i32) -> u64 {
+    u32_to_f64_scaled(i.unsigned_abs(), exp) | ((i < 0) as u64) << 63
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_to_f64_scaled(i: i64, exp: i32) -> u64 {
+    u64_to_f64_scaled(i.unsigned_abs(), exp) | ((i < 0) as u64) << 63
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_to_f64_scaled(i: i128, exp: i32) -> u64 {
+    u128_to_f64_scaled(i.unsigned_abs(), exp) | ((i < 0) as u64) << 63
+}
+
+/// Rounds m × 2<sup>e</sup>, plus a tiny non-zero amount if `sticky` is set,
+/// to an f64, and returns its bits.
+#[cfg_attr(not(noinline), inline)]
+pub(crate) fn binary_to_f64(negative: bool, m: u64, e: i32, sticky: bool, round: Round) -> u64 {
+    let sign_bit = (negative as u64) << 63;
+    let n = 63 - m.leading_zeros() as i32; // Position of the highest 1-bit.
+    let exp = n + e; // The value is >= 2^exp, < 2^(exp + 1).
+    let s = exp.max(-1022) - 52 - e; // Number of insignificant bits.
+    let (t, half, inexact) = if s <= 0 {
+        (m << -s, Ordering::Less, sticky) // Exact, apart from the sticky bit.
+    } else if s > 64 {
+        (0, Ordering::Less, true)
+    } else {
+        let r = m.checked_shl(64 - s as u32).unwrap_or(0); // Insignificant bits, only relevant for rounding.
+        let half = if r == 1 << 63 && sticky { Ordering::Greater } else { r.cmp(&(1 << 63)) };
+        (m.checked_shr(s as u32).unwrap_or(0), half, r != 0 || sticky)
+    };
+    let t = t + round.round_up(negative, t & 1 == 1, half, inexact) as u64;
+    if exp > 1023 {
+        return overflow_f64(negative, round);
+    }
+    let a = (((exp.max(-1022) + 1022) as u64) << 52) + t; // + not |, so the mantissa can overflow into the exponent.
+    if a >= 2047 << 52 {
+        overflow_f64(negative, round)
+    } else {
+        a | sign_bit
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+fn overflow_f64(negative: bool, round: Round) -> u64 {
+    let sign_bit = (negative as u64) << 63;
+    if round.overflow_to_infinity(negative) {
+        2047 << 52 | sign_bit
+    } else {
+        ((2047 << 52) - 1) | sign_bit // The largest finite value.
+    }
+}
+
+/// Rounds m × 2<sup>e</sup>, plus a tiny non-zero amount if `sticky` is set,
+/// to an f32, and returns its bits.
+#[cfg_attr(not(noinline), inline)]
+fn binary_to_f32(negative: bool, m: u64, e: i32, sticky: bool, round: Round) -> u32 {
+    let sign_bit = (negative as u32) << 31;
+    let n = 63 - m.leading_zeros() as i32; // Position of the highest 1-bit.
+    let exp = n + e; // The value is >= 2^exp, < 2^(exp + 1).
+    let s = exp.max(-126) - 23 - e; // Number of insignificant bits.
+    let (t, half, inexact) = if s <= 0 {
+        (m << -s, Ordering::Less, sticky) // Exact, apart from the sticky bit.
+    } else if s > 64 {
+        (0, Ordering::Less, true)
+    } else {
+        let r = m.checked_shl(64 - s as u32).unwrap_or(0); // Insignificant bits, only relevant for rounding.
+        let half = if r == 1 << 63 && sticky { Ordering::Greater } else { r.cmp(&(1 << 63)) };
+        (m.checked_shr(s as u32).unwrap_or(0), half, r != 0 || sticky)
+    };
+    let t = t + round.round_up(negative, t & 1 == 1, half, inexact) as u64;
+    if exp > 127 {
+        return overflow_f32(negative, round);
+    }
+    let a = (((exp.max(-126) + 126) as u32) << 23) + t as u32; // + not |, so the mantissa can overflow into the exponent.
+    if a >= 255 << 23 {
+        overflow_f32(negative, round)
+    } else {
+        a | sign_bit
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+fn overflow_f32(negative: bool, round: Round) -> u32 {
+    let sign_bit = (negative as u32) << 31;
+    if round.overflow_to_infinity(negative) {
+        255 << 23 | sign_bit
+    } else {
+        ((255 << 23) - 1) | sign_bit // The largest finite value.
+    }
+}
+
+/// Multiplies an f64 by 2^e and rounds it to an integer, saturating at
+/// `u128::MAX`. Returns its sign and magnitude. NaN results in zero.
+#[cfg_attr(not(noinline), inline)]
+fn f64_scaled_to_u128(f: u64, e: i32, round: Round) -> (bool, u128) {
+    let negative = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let (m, e) = if a < 1 << 52 { // Zero or subnormal
+        (a, e - 1074)
+    } else if a < 2047 << 52 { // Normal
+        (a & !0 >> 12 | 1 << 52, e + (a >> 52) as i32 - 1075)
+    } else if a == 2047 << 52 { // Infinity
+        return (negative, u128::MAX);
+    } else { // NaN
+        return (false, 0);
+    };
+    if m == 0 {
+        (negative, 0)
+    } else if e > 75 { // At least 2^128
+        (negative, u128::MAX)
+    } else if e >= 0 { // Exact
+        (negative, (m as u128) << e)
+    } else {
+        let s = -e as u32; // Number of fractional bits.
+        let q = m.checked_shr(s).unwrap_or(0);
+        let r = if s < 64 { m & !(!0 << s) } else { m }; // Fractional bits.
+        let half = if s > 64 { Ordering::Less } else { r.cmp(&(1 << (s - 1))) };
+        (negative, (q + round.round_up(negative, q & 1 != 0, half, r != 0) as u64).into())
+    }
+}
+
+/// Clamps a number of fractional bits of a fixed point number. Larger values
+/// make no difference.
+#[cfg_attr(not(noinline), inline)]
+pub(crate) fn fixed_exponent(frac: u32) -> i32 {
+    frac.min(2048) as i32
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_fixed_to_f32(x: u16, frac: u32) -> u32 {
+    u16_to_f32_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_fixed_to_f32(i: i16, frac: u32) -> u32 {
+    i16_to_f32_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_fixed_to_f32(x: u32, frac: u32) -> u32 {
+    u32_to_f32_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_fixed_to_f32(i: i32, frac: u32) -> u32 {
+    i32_to_f32_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_fixed_to_f32(x: u64, frac: u32) -> u32 {
+    u64_to_f32_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_fixed_to_f32(i: i64, frac: u32) -> u32 {
+    i64_to_f32_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_fixed_to_f32(x: u128, frac: u32) -> u32 {
+    u128_to_f32_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_fixed_to_f32(i: i128, frac: u32) -> u32 {
+    i128_to_f32_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u16_fixed_to_f64(x: u16, frac: u32) -> u64 {
+    u16_to_f64_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i16_fixed_to_f64(i: i16, frac: u32) -> u64 {
+    i16_to_f64_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u32_fixed_to_f64(x: u32, frac: u32) -> u64 {
+    u32_to_f64_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i32_fixed_to_f64(i: i32, frac: u32) -> u64 {
+    i32_to_f64_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u64_fixed_to_f64(x: u64, frac: u32) -> u64 {
+    u64_to_f64_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i64_fixed_to_f64(i: i64, frac: u32) -> u64 {
+    i64_to_f64_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn u128_fixed_to_f64(x: u128, frac: u32) -> u64 {
+    u128_to_f64_scaled(x, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn i128_fixed_to_f64(i: i128, frac: u32) -> u64 {
+    i128_to_f64_scaled(i, -fixed_exponent(frac))
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_u16_fixed(f: u32, frac: u32, round: Round) -> u16 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a.min(u16::MAX.into()) as u16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_i16_fixed(f: u32, frac: u32, round: Round) -> i16 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, a) => (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg(),
+        (false, a) => a.min(i16::MAX as u128) as i16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_u32_fixed(f: u32, frac: u32, round: Round) -> u32 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a.min(u32::MAX.into()) as u32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_i32_fixed(f: u32, frac: u32, round: Round) -> i32 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, a) => (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg(),
+        (false, a) => a.min(i32::MAX as u128) as i32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_u64_fixed(f: u32, frac: u32, round: Round) -> u64 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a.min(u64::MAX.into()) as u64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_i64_fixed(f: u32, frac: u32, round: Round) -> i64 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, a) => (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg(),
+        (false, a) => a.min(i64::MAX as u128) as i64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_u128_fixed(f: u32, frac: u32, round: Round) -> u128 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_i128_fixed(f: u32, frac: u32, round: Round) -> i128 {
+    match f64_scaled_to_u128(f32_to_f64(f), fixed_exponent(frac), round) {
+        (true, a) => (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg(),
+        (false, a) => a.min(i128::MAX as u128) as i128,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_u16_fixed(f: u64, frac: u32, round: Round) -> u16 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a.min(u16::MAX.into()) as u16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_i16_fixed(f: u64, frac: u32, round: Round) -> i16 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, a) => (a.min(i16::MIN.unsigned_abs().into()) as i16).wrapping_neg(),
+        (false, a) => a.min(i16::MAX as u128) as i16,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_u32_fixed(f: u64, frac: u32, round: Round) -> u32 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a.min(u32::MAX.into()) as u32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_i32_fixed(f: u64, frac: u32, round: Round) -> i32 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, a) => (a.min(i32::MIN.unsigned_abs().into()) as i32).wrapping_neg(),
+        (false, a) => a.min(i32::MAX as u128) as i32,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_u64_fixed(f: u64, frac: u32, round: Round) -> u64 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a.min(u64::MAX.into()) as u64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_i64_fixed(f: u64, frac: u32, round: Round) -> i64 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, a) => (a.min(i64::MIN.unsigned_abs().into()) as i64).wrapping_neg(),
+        (false, a) => a.min(i64::MAX as u128) as i64,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_u128_fixed(f: u64, frac: u32, round: Round) -> u128 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, _) => 0,
+        (false, a) => a,
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_i128_fixed(f: u64, frac: u32, round: Round) -> i128 {
+    match f64_scaled_to_u128(f, fixed_exponent(frac), round) {
+        (true, a) => (a.min(i128::MIN.unsigned_abs()) as i128).wrapping_neg(),
+        (false, a) => a.min(i128::MAX as u128) as i128,
+    }
+}
+
+/// Divides two non-zero integers, and returns the quotient as m × 2^e (with
+/// m having at least 63 significant bits), and whether there is a remainder.
+#[cfg_attr(not(noinline), inline)]
+fn ratio(num: u128, den: u128) -> (u64, i32, bool) {
+    let l = num.leading_zeros();
+    let a = num << l;
+    if den >> 64 == 0 {
+        // The quotient has at least 64 bits.
+        let q = a / den;
+        let s = 64 - q.leading_zeros(); // Number of bits that don't fit in a u64.
+        ((q >> s) as u64, s as i32 - l as i32, !a.is_multiple_of(den) || q & !(!0 << s) != 0)
+    } else {
+        // Long division, one bit at a time, of two numbers with their highest bit set.
+        let k = den.leading_zeros();
+        let b = den << k;
+        let mut r = a;
+        let mut q = 0u64;
+        let mut carry = false;
+        for _ in 0..64 {
+            let bit = carry || r >= b;
+            if bit { r = r.wrapping_sub(b); }
+            q = q << 1 | bit as u64;
+            carry = r >> 127 != 0;
+            r <<= 1;
+        }
+        (q, k as i32 - l as i32 - 63, r != 0 || carry)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+fn ratio_to_f64(negative: bool, num: u128, den: u128, round: Round) -> u64 {
+    if num == 0 {
+        if den == 0 { 0x7FF8 << 48 } else { 0 }
+    } else if den == 0 {
+        0x7FF << 52 | (negative as u64) << 63
+    } else {
+        let (m, e, sticky) = ratio(num, den);
+        binary_to_f64(negative, m, e, sticky, round)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+fn ratio_to_f32(negative: bool, num: u128, den: u128, round: Round) -> u32 {
+    if num == 0 {
+        if den == 0 { 0x7FC0_0000 } else { 0 }
+    } else if den == 0 {
+        0xFF << 23 | (negative as u32) << 31
+    } else {
+        let (m, e, sticky) = ratio(num, den);
+        binary_to_f32(negative, m, e, sticky, round)
+    }
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u8_to_f32(num: u8, den: u8, round: Round) -> u32 {
+    ratio_to_f32(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u16_to_f32(num: u16, den: u16, round: Round) -> u32 {
+    ratio_to_f32(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u32_to_f32(num: u32, den: u32, round: Round) -> u32 {
+    ratio_to_f32(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u64_to_f32(num: u64, den: u64, round: Round) -> u32 {
+    ratio_to_f32(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u128_to_f32(num: u128, den: u128, round: Round) -> u32 {
+    ratio_to_f32(false, num, den, round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i8_to_f32(num: i8, den: i8, round: Round) -> u32 {
+    ratio_to_f32((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i16_to_f32(num: i16, den: i16, round: Round) -> u32 {
+    ratio_to_f32((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i32_to_f32(num: i32, den: i32, round: Round) -> u32 {
+    ratio_to_f32((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i64_to_f32(num: i64, den: i64, round: Round) -> u32 {
+    ratio_to_f32((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i128_to_f32(num: i128, den: i128, round: Round) -> u32 {
+    ratio_to_f32((num < 0) != (den < 0), num.unsigned_abs(), den.unsigned_abs(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u8_to_f64(num: u8, den: u8, round: Round) -> u64 {
+    ratio_to_f64(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u16_to_f64(num: u16, den: u16, round: Round) -> u64 {
+    ratio_to_f64(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u32_to_f64(num: u32, den: u32, round: Round) -> u64 {
+    ratio_to_f64(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u64_to_f64(num: u64, den: u64, round: Round) -> u64 {
+    ratio_to_f64(false, num.into(), den.into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_u128_to_f64(num: u128, den: u128, round: Round) -> u64 {
+    ratio_to_f64(false, num, den, round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i8_to_f64(num: i8, den: i8, round: Round) -> u64 {
+    ratio_to_f64((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i16_to_f64(num: i16, den: i16, round: Round) -> u64 {
+    ratio_to_f64((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i32_to_f64(num: i32, den: i32, round: Round) -> u64 {
+    ratio_to_f64((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i64_to_f64(num: i64, den: i64, round: Round) -> u64 {
+    ratio_to_f64((num < 0) != (den < 0), num.unsigned_abs().into(), den.unsigned_abs().into(), round)
+}
+
+#[cfg_attr(not(noinline), inline)]
+pub fn ratio_i128_to_f64(num: i128, den: i128, round: Round) -> u64 {
+    ratio_to_f64((num < 0) != (den < 0), num.unsigned_abs(), den.unsigned_abs(), round)
+}
+
+/// The parts of a floating point number, as returned by [`decode_f32`] and
+/// [`decode_f64`].
+///
+/// For finite numbers, the value is ±`mantissa` × 2<sup>`exponent`</sup>.
+/// Zero, infinity and NaN have an exponent of zero, and only NaN has a
+/// non-zero mantissa: its payload, including the quiet bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// Whether the sign bit is set.
+    pub sign: bool,
+    /// The significand, including the implicit 1-bit of normal numbers.
+    pub mantissa: u64,
+    /// The exponent of the lowest bit of the mantissa.
+    pub exponent: i16,
+    /// The kind of number.
+    pub class: FpCategory,
+}
+
+/// Splits the bits of an `f32` into its sign, mantissa and exponent.
+#[cfg_attr(not(noinline), inline)]
+pub fn decode_f32(f: u32) -> Decoded {
+    let sign = f >> 31 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let (mantissa, exponent, class) = if a == 0 {
+        (0, 0, FpCategory::Zero)
+    } else if a < 1 << 23 {
+        (a, -149, FpCategory::Subnormal)
+    } else if a < 255 << 23 {
+        (a & !0 >> 9 | 1 << 23, (a >> 23) as i16 - 150, FpCategory::Normal)
+    } else if a == 255 << 23 {
+        (0, 0, FpCategory::Infinite)
+    } else {
+        (a & !0 >> 9, 0, FpCategory::Nan)
+    };
+    Decoded { sign, mantissa: mantissa.into(), exponent, class }
+}
+
+/// Splits the bits of an `f64` into its sign, mantissa and exponent.
+#[cfg_attr(not(noinline), inline)]
+pub fn decode_f64(f: u64) -> Decoded {
+    let sign = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let (mantissa, exponent, class) = if a == 0 {
+        (0, 0, FpCategory::Zero)
+    } else if a < 1 << 52 {
+        (a, -1074, FpCategory::Subnormal)
+    } else if a < 2047 << 52 {
+        (a & !0 >> 12 | 1 << 52, (a >> 52) as i16 - 1075, FpCategory::Normal)
+    } else if a == 2047 << 52 {
+        (0, 0, FpCategory::Infinite)
+    } else {
+        (a & !0 >> 12, 0, FpCategory::Nan)
+    };
+    Decoded { sign, mantissa, exponent, class }
+}
+
+/// Rounds ±`mantissa` × 2<sup>`exponent`</sup> to the closest `f32`
+/// (breaking ties to even), which might be subnormal, zero or infinity.
+#[cfg_attr(not(noinline), inline)]
+pub fn encode_f32(sign: bool, mantissa: u128, exponent: i32) -> u32 {
+    u128_scaled_to_f32(mantissa, exponent) | (sign as u32) << 31
+}
+
+/// Rounds ±`mantissa` × 2<sup>`exponent`</sup> to the closest `f64`
+/// (breaking ties to even), which might be subnormal, zero or infinity.
+#[cfg_attr(not(noinline), inline)]
+pub fn encode_f64(sign: bool, mantissa: u128, exponent: i32) -> u64 {
+    u128_scaled_to_f64(mantissa, exponent) | (sign as u64) << 63
+}
+
+/// Returns the highest 128 bits of a little endian slice of limbs, shifted
+/// such that the highest bit is set, with the lowest bit set if any of the
+/// remaining bits are set. Also returns the exponent of the lowest bit.
+#[cfg_attr(not(noinline), inline)]
+fn limbs_top(limbs: &[u64]) -> (u128, i32) {
+    let i = match limbs.iter().rposition(|&l| l != 0) {
+        Some(i) => i, // Index of the highest non-zero limb.
+        None => return (0, 0),
+    };
+    let limb = |k: usize| i.checked_sub(k).map_or(0, |j| limbs[j]);
+    let n = limbs[i].leading_zeros();
+    let x = ((limbs[i] as u128) << 64 | limb(1) as u128) << n | (limb(2) >> 1 >> (63 - n)) as u128;
+    let sticky = limb(2) << n != 0 || limbs[..i.saturating_sub(2)].iter().any(|&l| l != 0);
+    let e = 64 * (i.min(64) as i32 - 1) - n as i32; // Anything above 2^4000 overflows anyway.
+    (x | sticky as u128, e)
+}
+
+/// Converts a little endian slice of 64-bit limbs, representing the magnitude
+/// of an integer, to the closest `f32` (breaking ties to even), or infinity.
+///
+/// The sign bit is set if `negative` is set, even for zero.
+#[cfg_attr(not(noinline), inline)]
+pub fn limbs_to_f32(limbs: &[u64], negative: bool) -> u32 {
+    let (m, e) = limbs_top(limbs);
+    u128_scaled_to_f32(m, e) | (negative as u32) << 31
+}
+
+/// Converts a little endian slice of 64-bit limbs, representing the magnitude
+/// of an integer, to the closest `f64` (breaking ties to even), or infinity.
+///
+/// The sign bit is set if `negative` is set, even for zero.
+#[cfg_attr(not(noinline), inline)]
+pub fn limbs_to_f64(limbs: &[u64], negative: bool) -> u64 {
+    let (m, e) = limbs_top(limbs);
+    u128_scaled_to_f64(m, e) | (negative as u64) << 63
+}
+
+/// The sign of an integer written by [`f64_to_limbs`] and similar functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sign {
+    /// Less than zero.
+    Negative,
+    /// Zero, including when a negative value was rounded to zero.
+    Zero,
+    /// Greater than zero.
+    Positive,
+}
+
+/// The reason [`f64_to_limbs`] and similar functions failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LimbsError {
+    /// The value is infinity or NaN.
+    NotFinite,
+    /// The integer does not fit in the given limbs.
+    TooShort,
+}
+
+/// Rounds the bits of an `f32` to an integer according to `round`, and
+/// writes its magnitude into `out` as little endian 64-bit limbs.
+///
+/// All limbs are written, and `out` is left untouched on failure.
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_limbs_rounded(f: u32, out: &mut [u64], round: Round) -> Result<Sign, LimbsError> {
+    f64_to_limbs_rounded(f32_to_f64(f), out, round) // Lossless conversion to f64, so only rounds once.
+}
+
+/// Rounds the bits of an `f64` to an integer according to `round`, and
+/// writes its magnitude into `out` as little endian 64-bit limbs.
+///
+/// All limbs are written, and `out` is left untouched on failure.
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_limbs_rounded(f: u64, out: &mut [u64], round: Round) -> Result<Sign, LimbsError> {
+    let negative = f >> 63 != 0;
+    let a = f & !0 >> 1; // Remove sign bit.
+    let (m, s) = if a >= 2047 << 52 { // Infinity or NaN
+        return Err(LimbsError::NotFinite);
+    } else if a >= 1075 << 52 { // At least 2^52, so already an integer
+        (a & !0 >> 12 | 1 << 52, (a >> 52) as u32 - 1075)
+    } else { // Might have a fractional part
+        (f64_scaled_to_u128(f, 0, round).1 as u64, 0)
+    };
+    if m == 0 {
+        out.iter_mut().for_each(|l| *l = 0);
+        return Ok(Sign::Zero);
+    }
+    let bits = 64 - m.leading_zeros() + s;
+    if bits as usize > out.len() * 64 {
+        return Err(LimbsError::TooShort);
+    }
+    let x = (m as u128) << (s % 64); // The non-zero bits, starting at limb s / 64.
+    for (i, l) in out.iter_mut().enumerate() {
+        *l = match i.wrapping_sub(s as usize / 64) {
+            0 => x as u64,
+            1 => (x >> 64) as u64,
+            _ => 0,
+        };
+    }
+    Ok(if negative { Sign::Negative } else { Sign::Positive })
+}
+
+/// Truncates the bits of an `f32` to an integer, and writes its magnitude into
+/// `out` as little endian 64-bit limbs.
+///
+/// All limbs are written, and `out` is left untouched on failure.
+#[cfg_attr(not(noinline), inline)]
+pub fn f32_to_limbs(f: u32, out: &mut [u64]) -> Result<Sign, LimbsError> {
+    f32_to_limbs_rounded(f, out, Round::TowardZero)
+}
+
+/// Truncates the bits of an `f64` to an integer, and writes its magnitude into
+/// `out` as little endian 64-bit limbs.
+///
+/// All limbs are written, and `out` is left untouched on failure.
+#[cfg_attr(not(noinline), inline)]
+pub fn f64_to_limbs(f: u64, out: &mut [u64]) -> Result<Sign, LimbsError> {
+    f64_to_limbs_rounded(f, out, Round::TowardZero)
+}
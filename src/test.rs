@@ -445,3 +445,2364 @@ fn test_i128() {
         assert_eq!(soft::f64_to_i128((i as f64).to_bits()), i as f64 as i128);
     }
 }
+
+#[test]
+fn test_slice() {
+    let u64s = [
+        0,
+        1,
+        u64::MAX,
+        u64::MAX / 2,
+        1 << 63,
+        (1 << 53) + 1,
+        0b1000000000000000000000011111111111111111111111111111111111111111,
+        0b1111111111111111111111110111111111111111111111111111111111111111,
+        0b10000000000000000000000000000000000000000000000000001100,
+        123456789 << 20 | 4321,
+        0xFFFF_FFFF,
+    ];
+    let mut f64s = [0.0; 11];
+    fast::slice::u64_to_f64(&u64s, &mut f64s);
+    for (&i, &f) in u64s.iter().zip(&f64s) {
+        assert_eq!(f, i as f64, "{} -> f64", i);
+    }
+    let i64s = u64s.map(|i| i as i64);
+    fast::slice::i64_to_f64(&i64s, &mut f64s);
+    for (&i, &f) in i64s.iter().zip(&f64s) {
+        assert_eq!(f, i as f64, "{} -> f64", i);
+    }
+    let u32s = u64s.map(|i| (i >> 20) as u32);
+    let mut f32s = [0.0; 11];
+    fast::slice::u32_to_f32(&u32s, &mut f32s);
+    for (&i, &f) in u32s.iter().zip(&f32s) {
+        assert_eq!(f, i as f32, "{} -> f32", i);
+    }
+    let f32s = [
+        0.0, -0.0, 1.5, -1.5, 2147483520.0, 2147483648.0, -2147483648.0, -2147483904.0,
+        f32::INFINITY, f32::NEG_INFINITY, f32::NAN, -f32::NAN, 1e-30, -123456.7, 0.99,
+    ];
+    let mut i32s = [0; 15];
+    fast::slice::f32_to_i32(&f32s, &mut i32s);
+    for (&f, &i) in f32s.iter().zip(&i32s) {
+        assert_eq!(i, f as i32, "{:?} -> i32", f);
+    }
+    let mut u128s = [0; 15];
+    fast::slice::f32_to_u128(&f32s, &mut u128s);
+    for (&f, &i) in f32s.iter().zip(&u128s) {
+        assert_eq!(i, f as u128, "{:?} -> u128", f);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_slice_length_mismatch() {
+    fast::slice::u64_to_f64(&[1, 2, 3], &mut [0.0; 2]);
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn test_simd() {
+    use core::simd::prelude::*;
+    let u128s = [
+        0,
+        1,
+        3,
+        u128::MAX,
+        u128::MAX / 2,
+        1 << 127,
+        1 << 64,
+        (1 << 64) - 1,
+        (1 << 53) + 1,
+        u128::from(u64::MAX) << 53,
+        u128::from(u64::MAX >> 12) << 51,
+        0b10000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000000000001,
+        0b10000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000,
+        0b1000000000000000000000000000000000000000000000000000000000000000000000000111111111111111111111111,
+        u128::MAX - (u128::MAX >> 24),
+        u128::MAX - (u128::MAX >> 23),
+    ];
+    let h = Simd::<u64, 16>::from_array(u128s.map(|i| (i >> 64) as u64));
+    let l = Simd::<u64, 16>::from_array(u128s.map(|i| i as u64));
+    let f32s = simd::u128_to_f32((h, l)).to_array();
+    let f64s = simd::u128_to_f64((h, l)).to_array();
+    let i32s = simd::i128_to_f32((h.cast(), l)).to_array();
+    let i64s = simd::i128_to_f64((h.cast(), l)).to_array();
+    let u = simd::u64_to_f32(h ^ l).to_array();
+    let v = simd::u64_to_f64(h ^ l).to_array();
+    let w = simd::i64_to_f32((h ^ l).cast()).to_array();
+    let x = simd::i64_to_f64((h ^ l).cast()).to_array();
+    for (k, &i) in u128s.iter().enumerate() {
+        assert_eq!(f32s[k].to_bits(), soft::u128_to_f32(i), "{} -> f32", i);
+        assert_eq!(f64s[k].to_bits(), soft::u128_to_f64(i), "{} -> f64", i);
+        assert_eq!(i32s[k].to_bits(), soft::i128_to_f32(i as i128), "{} -> f32", i as i128);
+        assert_eq!(i64s[k].to_bits(), soft::i128_to_f64(i as i128), "{} -> f64", i as i128);
+        let j = (i >> 64) as u64 ^ i as u64;
+        assert_eq!(u[k].to_bits(), soft::u64_to_f32(j), "{} -> f32", j);
+        assert_eq!(v[k].to_bits(), soft::u64_to_f64(j), "{} -> f64", j);
+        assert_eq!(w[k].to_bits(), soft::i64_to_f32(j as i64), "{} -> f32", j as i64);
+        assert_eq!(x[k].to_bits(), soft::i64_to_f64(j as i64), "{} -> f64", j as i64);
+    }
+    let f64s = [
+        0.0, -0.0, 0.5, -0.99, 1.0, -1.0, 1e10, -1e10, 1e19, -1e19, 1e30, -1e30, 1.7e38, -1.7e38, 1e300,
+        f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -f64::NAN, 9007199254740993.0, -9223372036854775808.0,
+        18446744073709549568.0, -170141183460469231731687303715884105728.0, 170141183460469231731687303715884105728.0,
+    ];
+    let f32s = f64s.map(|f| f as f32);
+    let a = simd::f32_to_u64(Simd::from_array(f32s)).to_array();
+    let b = simd::f32_to_i64(Simd::from_array(f32s)).to_array();
+    let (ch, cl) = simd::f32_to_u128(Simd::from_array(f32s));
+    let (dh, dl) = simd::f32_to_i128(Simd::from_array(f32s));
+    let e = simd::f64_to_u64(Simd::from_array(f64s)).to_array();
+    let g = simd::f64_to_i64(Simd::from_array(f64s)).to_array();
+    let (hh, hl) = simd::f64_to_u128(Simd::from_array(f64s));
+    let (ih, il) = simd::f64_to_i128(Simd::from_array(f64s));
+    for (k, (&f, &g32)) in f64s.iter().zip(&f32s).enumerate() {
+        assert_eq!(a[k], g32 as u64, "{:?} -> u64", g32);
+        assert_eq!(b[k], g32 as i64, "{:?} -> i64", g32);
+        assert_eq!((ch[k] as u128) << 64 | cl[k] as u128, g32 as u128, "{:?} -> u128", g32);
+        assert_eq!((dh[k] as i128) << 64 | dl[k] as i128, g32 as i128, "{:?} -> i128", g32);
+        assert_eq!(e[k], f as u64, "{:?} -> u64", f);
+        assert_eq!(g[k], f as i64, "{:?} -> i64", f);
+        assert_eq!((hh[k] as u128) << 64 | hl[k] as u128, f as u128, "{:?} -> u128", f);
+        assert_eq!((ih[k] as i128) << 64 | il[k] as i128, f as i128, "{:?} -> i128", f);
+    }
+}
+
+/// Rounds `x` to `bits` (at most 53) significant bits, breaking ties to even.
+fn round_to_bits(x: u128, bits: u32) -> f64 {
+    let n = 128 - x.leading_zeros();
+    if n <= bits {
+        return x as f64;
+    }
+    let s = n - bits;
+    let q = x >> s;
+    let r = x & ((1 << s) - 1);
+    let half = 1 << (s - 1);
+    let q = if r > half || (r == half && q & 1 == 1) { q + 1 } else { q };
+    q as f64 * 2f64.powi(s as i32)
+}
+
+/// Decodes the bits of an f16 into an (exactly equal) f64.
+fn f16_to_f64(f: u16) -> f64 {
+    let e = (f >> 10 & 31) as i32;
+    let m = (f & 0x3FF) as f64;
+    let v = match e {
+        0 => m * 2f64.powi(-24),
+        31 if m == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (m + 1024.0) * 2f64.powi(e - 25),
+    };
+    if f >> 15 == 1 { -v } else { v }
+}
+
+#[test]
+fn test_f16() {
+    let r = |x: u128| {
+        let r = round_to_bits(x, 11);
+        if r >= 65536.0 { f64::INFINITY } else { r }
+    };
+    for i in 0..=u16::MAX {
+        assert_eq!(f16_to_f64(soft::u16_to_f16(i)), r(i.into()), "{} -> f16", i);
+        let j = i as i16;
+        let v = if j < 0 { -r(j.unsigned_abs().into()) } else { r(j as u128) };
+        assert_eq!(f16_to_f64(soft::i16_to_f16(j)), v, "{} -> f16", j);
+        if i <= 255 {
+            assert_eq!(soft::u8_to_f16(i as u8), soft::u16_to_f16(i), "{} -> f16", i);
+            assert_eq!(soft::i8_to_f16(i as i8), soft::i16_to_f16(i as i8 as i16), "{} -> f16", i as i8);
+        }
+    }
+    for &i in &[
+        0,
+        1,
+        2048,
+        2049,
+        2051,
+        65504,
+        65519, // Largest value that doesn't round to infinity.
+        65520,
+        65535,
+        65536,
+        1 << 31,
+        u32::MAX as u128,
+        u64::MAX as u128,
+        u128::MAX,
+    ][..]
+    {
+        assert_eq!(f16_to_f64(soft::u128_to_f16(i)), r(i), "{} -> f16", i);
+        assert_eq!(soft::u64_to_f16(i as u64), soft::u128_to_f16((i as u64).into()), "{} -> f16", i as u64);
+        assert_eq!(soft::u32_to_f16(i as u32), soft::u128_to_f16((i as u32).into()), "{} -> f16", i as u32);
+        let j = (i >> 1) as i128;
+        assert_eq!(soft::i128_to_f16(-j), soft::u128_to_f16(j as u128) | ((j != 0) as u16) << 15, "{} -> f16", -j);
+        assert_eq!(soft::i64_to_f16(i as i64), soft::i128_to_f16(i as i64 as i128), "{} -> f16", i as i64);
+        assert_eq!(soft::i32_to_f16(i as i32), soft::i128_to_f16(i as i32 as i128), "{} -> f16", i as i32);
+    }
+    for i in 0..=u16::MAX {
+        let f = f16_to_f64(i);
+        assert_eq!(soft::f16_to_u8(i), f as u8, "{:?} -> u8", f);
+        assert_eq!(soft::f16_to_u16(i), f as u16, "{:?} -> u16", f);
+        assert_eq!(soft::f16_to_u32(i), f as u32, "{:?} -> u32", f);
+        assert_eq!(soft::f16_to_u64(i), f as u64, "{:?} -> u64", f);
+        assert_eq!(soft::f16_to_u128(i), f as u128, "{:?} -> u128", f);
+        assert_eq!(soft::f16_to_i8(i), f as i8, "{:?} -> i8", f);
+        assert_eq!(soft::f16_to_i16(i), f as i16, "{:?} -> i16", f);
+        assert_eq!(soft::f16_to_i32(i), f as i32, "{:?} -> i32", f);
+        assert_eq!(soft::f16_to_i64(i), f as i64, "{:?} -> i64", f);
+        assert_eq!(soft::f16_to_i128(i), f as i128, "{:?} -> i128", f);
+    }
+}
+
+/// Decodes the bits of a bfloat16 into an (exactly equal) f64.
+fn bf16_to_f64(f: u16) -> f64 {
+    f32::from_bits((f as u32) << 16) as f64
+}
+
+/// Reference implementation of soft::f32_to_bf16.
+fn f32_to_bf16_ref(f: u32) -> u16 {
+    if f32::from_bits(f).is_nan() {
+        return (f >> 16) as u16 | 0x40;
+    }
+    let a = (f >> 16) as u16;
+    let b = f & 0xFFFF;
+    if b > 0x8000 || (b == 0x8000 && a & 1 == 1) { a + 1 } else { a }
+}
+
+#[test]
+fn test_bf16() {
+    let r = |x: u128| {
+        let r = round_to_bits(x, 8);
+        if r >= 2f64.powi(128) { f64::INFINITY } else { r }
+    };
+    for i in 0..=u16::MAX {
+        assert_eq!(bf16_to_f64(soft::u16_to_bf16(i)), r(i.into()), "{} -> bf16", i);
+        let j = i as i16;
+        let v = if j < 0 { -r(j.unsigned_abs().into()) } else { r(j as u128) };
+        assert_eq!(bf16_to_f64(soft::i16_to_bf16(j)), v, "{} -> bf16", j);
+        if i <= 255 {
+            assert_eq!(soft::u8_to_bf16(i as u8), soft::u16_to_bf16(i), "{} -> bf16", i);
+            assert_eq!(soft::i8_to_bf16(i as i8), soft::i16_to_bf16(i as i8 as i16), "{} -> bf16", i as i8);
+        }
+    }
+    for &i in &[
+        0,
+        1,
+        255,
+        256,
+        257,
+        0b110000001, // Tie, round to even (up)
+        0b100000001, // Tie, round to even (down)
+        0b1000000011000000000000000000000000000000000001, // Round to closest (up), with sticky bit far away.
+        1 << 31,
+        u32::MAX as u128,
+        (u32::MAX as u128) << 24 | 0xFFFFFF,
+        0xFF80_0000_0000_0001,
+        0xFF80_0000_0000_0000,
+        0xFE80_0000_0000_0000,
+        u64::MAX as u128,
+        u128::MAX - (u128::MAX >> 9),
+        u128::MAX - (u128::MAX >> 8),
+        u128::MAX,
+    ][..]
+    {
+        assert_eq!(bf16_to_f64(soft::u128_to_bf16(i)), r(i), "{} -> bf16", i);
+        assert_eq!(bf16_to_f64(soft::u64_to_bf16(i as u64)), r((i as u64).into()), "{} -> bf16", i as u64);
+        assert_eq!(bf16_to_f64(soft::u32_to_bf16(i as u32)), r((i as u32).into()), "{} -> bf16", i as u32);
+        let j = (i >> 1) as i128;
+        assert_eq!(bf16_to_f64(soft::i128_to_bf16(-j)), -r(j as u128), "{} -> bf16", -j);
+        assert_eq!(soft::i64_to_bf16(-j as i64), soft::i128_to_bf16(-j as i64 as i128), "{} -> bf16", -j as i64);
+        assert_eq!(soft::i32_to_bf16(-j as i32), soft::i128_to_bf16(-j as i32 as i128), "{} -> bf16", -j as i32);
+    }
+    for i in 0..=u16::MAX {
+        let f = bf16_to_f64(i);
+        assert_eq!(soft::bf16_to_u8(i), f as u8, "{:?} -> u8", f);
+        assert_eq!(soft::bf16_to_u16(i), f as u16, "{:?} -> u16", f);
+        assert_eq!(soft::bf16_to_u32(i), f as u32, "{:?} -> u32", f);
+        assert_eq!(soft::bf16_to_u64(i), f as u64, "{:?} -> u64", f);
+        assert_eq!(soft::bf16_to_u128(i), f as u128, "{:?} -> u128", f);
+        assert_eq!(soft::bf16_to_i8(i), f as i8, "{:?} -> i8", f);
+        assert_eq!(soft::bf16_to_i16(i), f as i16, "{:?} -> i16", f);
+        assert_eq!(soft::bf16_to_i32(i), f as i32, "{:?} -> i32", f);
+        assert_eq!(soft::bf16_to_i64(i), f as i64, "{:?} -> i64", f);
+        assert_eq!(soft::bf16_to_i128(i), f as i128, "{:?} -> i128", f);
+        assert_eq!(fast::bf16_to_f32(i).to_bits(), (i as u32) << 16);
+    }
+    for &f in &[
+        0.0f32,
+        -0.0,
+        1.0,
+        f32::from_bits(0x3F80_8000), // Tie, round to even (down)
+        f32::from_bits(0x3F81_8000), // Tie, round to even (up)
+        f32::from_bits(0x3F80_8001), // Round to closest (up)
+        f32::from_bits(0x3F80_7FFF), // Round to closest (down)
+        f32::MAX,
+        f32::MIN,
+        f32::MIN_POSITIVE,
+        f32::from_bits(1),
+        f32::from_bits(0x8000_8000),
+        f32::from_bits(0x0000_8001),
+        f32::from_bits(0x007F_FFFF),
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        f32::NAN,
+        f32::from_bits(0x7F80_0001), // Signalling NaN
+        f32::from_bits(0xFFA1_2345),
+    ][..]
+    {
+        assert_eq!(soft::f32_to_bf16(f.to_bits()), f32_to_bf16_ref(f.to_bits()), "{:?} -> bf16", f);
+        assert_eq!(fast::f32_to_bf16(f), f32_to_bf16_ref(f.to_bits()), "{:?} -> bf16", f);
+    }
+}
+
+#[test]
+#[ignore]
+fn test_all_f32_to_bf16() {
+    for i in 0..=u32::MAX {
+        assert_eq!(soft::f32_to_bf16(i), f32_to_bf16_ref(i), "{:?} -> bf16", f32::from_bits(i));
+        assert_eq!(fast::f32_to_bf16(f32::from_bits(i)), f32_to_bf16_ref(i), "{:?} -> bf16", f32::from_bits(i));
+    }
+}
+
+/// Converts the bits of an f64 into the bits of an (exactly equal) f128.
+fn f64_to_f128(f: u64) -> u128 {
+    let s = ((f >> 63) as u128) << 127;
+    let e = f >> 52 & 2047;
+    let m = (f as u128) << 76 >> 16;
+    match e {
+        0 if m == 0 => s,
+        0 => {
+            let n = m.leading_zeros() - 15; // Normalize the subnormal mantissa.
+            s | (15361 - n as u128) << 112 | (m << n) & ((1 << 112) - 1)
+        }
+        2047 => s | 32767 << 112 | m,
+        _ => s | (e as u128 + 15360) << 112 | m,
+    }
+}
+
+/// Reference implementation of soft::u128_to_f128.
+fn u128_to_f128_ref(x: u128) -> u128 {
+    if x == 0 {
+        return 0;
+    }
+    let n = 128 - x.leading_zeros();
+    if n <= 113 {
+        return (16382 + n as u128) << 112 | (x << (113 - n)) & ((1 << 112) - 1);
+    }
+    let s = n - 113;
+    let q = x >> s;
+    let r = x & ((1 << s) - 1);
+    let half = 1 << (s - 1);
+    let q = if r > half || (r == half && q & 1 == 1) { q + 1 } else { q };
+    let (q, n) = if q >> 113 != 0 { (q >> 1, n + 1) } else { (q, n) };
+    (16382 + n as u128) << 112 | q & ((1 << 112) - 1)
+}
+
+#[test]
+fn test_f128() {
+    for &i in &[
+        0,
+        1,
+        2,
+        3,
+        1234,
+        (1 << 53) + 1,
+        u64::MAX as u128,
+        (u64::MAX as u128) << 49 | 0x1_FFFF_FFFF_FFFF, // Exactly 113 bits.
+        (1 << 113) + 1, // Round to closest (down)
+        (1 << 113) + 3, // Tie, round to even (up)
+        (1 << 114) + 2, // Tie, round to even (down)
+        (1 << 114) + 3, // Round to closest (up)
+        (1 << 127) + (1 << 14), // Tie, round to even (down)
+        (1 << 127) + (1 << 14) + 1, // Round to closest (up), with sticky bit far away.
+        (1 << 127) + (3 << 14), // Tie, round to even (up)
+        u128::MAX >> 1,
+        u128::MAX - 1,
+        u128::MAX,
+    ][..]
+    {
+        let f = soft::u128_to_f128(i);
+        assert_eq!(f, u128_to_f128_ref(i), "{} -> f128", i);
+        let j = i >> 1;
+        assert_eq!(soft::i128_to_f128(-(j as i128)), u128_to_f128_ref(j) | ((j != 0) as u128) << 127, "{} -> f128", -(j as i128));
+        assert_eq!(soft::i128_to_f128(j as i128), u128_to_f128_ref(j), "{} -> f128", j);
+        let e = (f >> 112) as i32 - 16383 - 112;
+        let m = 1 << 112 | f & ((1 << 112) - 1);
+        let r = match e {
+            _ if i == 0 => 0,
+            16.. => u128::MAX, // Rounded up to 2^128.
+            0.. => m << e,
+            _ => m >> -e,
+        };
+        assert_eq!(soft::f128_to_u128(f), r, "{} -> f128 -> u128", i);
+        let n = if r >= 1 << 127 { i128::MIN } else { -(r as i128) };
+        assert_eq!(soft::f128_to_i128(f | 1 << 127), n, "-{} -> f128 -> i128", i);
+        assert_eq!(soft::f128_to_u64(f), r.min(u64::MAX.into()) as u64, "{} -> f128 -> u64", i);
+        let k = i as u64;
+        assert_eq!(soft::u64_to_f128(k), u128_to_f128_ref(k.into()), "{} -> f128", k);
+        assert_eq!(soft::i64_to_f128(k as i64), soft::i128_to_f128(k as i64 as i128), "{} -> f128", k as i64);
+        let k = i as u32;
+        assert_eq!(soft::u32_to_f128(k), u128_to_f128_ref(k.into()), "{} -> f128", k);
+        assert_eq!(soft::i32_to_f128(k as i32), soft::i128_to_f128(k as i32 as i128), "{} -> f128", k as i32);
+        let k = i as u16;
+        assert_eq!(soft::u16_to_f128(k), u128_to_f128_ref(k.into()), "{} -> f128", k);
+        assert_eq!(soft::i16_to_f128(k as i16), soft::i128_to_f128(k as i16 as i128), "{} -> f128", k as i16);
+        let k = i as u8;
+        assert_eq!(soft::u8_to_f128(k), u128_to_f128_ref(k.into()), "{} -> f128", k);
+        assert_eq!(soft::i8_to_f128(k as i8), soft::i128_to_f128(k as i8 as i128), "{} -> f128", k as i8);
+    }
+    for &f in &[
+        0.0, -0.0, 0.5, -0.99, 1.0, -1.0, 1.5, 127.9, -128.0, -128.5, 255.9, 256.0, 32767.5, -32769.0,
+        65535.9, 1e10, -1e10, 4294967295.5, 1e19, -1e19, 9223372036854775807.0, 1e30, -1e30, 1.7e38, -1.7e38,
+        3.4e38, -3.4e38, 1e300, -1e300, 5e-324, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -f64::NAN,
+    ][..]
+    {
+        let g = f64_to_f128(f.to_bits());
+        assert_eq!(soft::f128_to_u8(g), f as u8, "{:?} -> u8", f);
+        assert_eq!(soft::f128_to_u16(g), f as u16, "{:?} -> u16", f);
+        assert_eq!(soft::f128_to_u32(g), f as u32, "{:?} -> u32", f);
+        assert_eq!(soft::f128_to_u64(g), f as u64, "{:?} -> u64", f);
+        assert_eq!(soft::f128_to_u128(g), f as u128, "{:?} -> u128", f);
+        assert_eq!(soft::f128_to_i8(g), f as i8, "{:?} -> i8", f);
+        assert_eq!(soft::f128_to_i16(g), f as i16, "{:?} -> i16", f);
+        assert_eq!(soft::f128_to_i32(g), f as i32, "{:?} -> i32", f);
+        assert_eq!(soft::f128_to_i64(g), f as i64, "{:?} -> i64", f);
+        assert_eq!(soft::f128_to_i128(g), f as i128, "{:?} -> i128", f);
+        if f.fract() == 0.0 && f.abs() < 1e30 && f != 0.0 {
+            assert_eq!(soft::i128_to_f128(f as i128), g, "{} -> f128", f as i128);
+        }
+    }
+}
+
+/// Converts the bits of an f64 into an (exactly equal) f80.
+fn f64_to_f80(f: u64) -> (u64, u16) {
+    let s = ((f >> 63) as u16) << 15;
+    let e = (f >> 52 & 2047) as u16;
+    let m = f << 11 | 1 << 63;
+    match e {
+        0 if f << 1 == 0 => (0, s),
+        0 => {
+            let n = (f << 12).leading_zeros() + 1; // Normalize the subnormal mantissa.
+            (f << 11 << n, (15361 - n as u16) | s)
+        }
+        2047 => (m, 32767 | s),
+        _ => (m, (e + 15360) | s),
+    }
+}
+
+/// Reference implementation of soft::u128_to_f80.
+fn u128_to_f80_ref(x: u128) -> (u64, u16) {
+    if x == 0 {
+        return (0, 0);
+    }
+    let n = 128 - x.leading_zeros();
+    if n <= 64 {
+        return (((x as u64) << (64 - n)), 16382 + n as u16);
+    }
+    let s = n - 64;
+    let q = x >> s;
+    let r = x & ((1 << s) - 1);
+    let half = 1 << (s - 1);
+    let q = if r > half || (r == half && q & 1 == 1) { q + 1 } else { q };
+    if q >> 64 != 0 { (1 << 63, 16383 + n as u16) } else { (q as u64, 16382 + n as u16) }
+}
+
+#[test]
+fn test_f80() {
+    for &i in &[
+        0,
+        1,
+        2,
+        3,
+        1234,
+        (1 << 53) + 1,
+        u32::MAX as u128,
+        u64::MAX as u128,
+        (1 << 64) + 1, // Round to closest (down)
+        (1 << 64) + 3, // Tie, round to even (up)
+        (1 << 65) + 2, // Tie, round to even (down)
+        (1 << 65) + 3, // Round to closest (up)
+        (1 << 127) + (1 << 63) + 1, // Round to closest (up), with sticky bit far away.
+        (u64::MAX as u128) << 64 | 1 << 63, // Tie, rounds up and overflows the mantissa.
+        u128::MAX >> 1,
+        u128::MAX,
+    ][..]
+    {
+        assert_eq!(soft::u128_to_f80(i), u128_to_f80_ref(i), "{} -> f80", i);
+        let j = i >> 1;
+        let (m, e) = u128_to_f80_ref(j);
+        assert_eq!(soft::i128_to_f80(-(j as i128)), (m, e | ((j != 0) as u16) << 15), "{} -> f80", -(j as i128));
+        assert_eq!(soft::i128_to_f80(j as i128), (m, e), "{} -> f80", j);
+        let k = i as u64;
+        assert_eq!(soft::u64_to_f80(k), u128_to_f80_ref(k.into()), "{} -> f80", k);
+        assert_eq!(soft::f80_to_u64(soft::u64_to_f80(k)), k, "{} -> f80 -> u64", k);
+        assert_eq!(soft::f80_to_i64(soft::i64_to_f80(k as i64)), k as i64, "{} -> f80 -> i64", k as i64);
+        assert_eq!(soft::u32_to_f80(k as u32), soft::u64_to_f80(k as u32 as u64), "{} -> f80", k as u32);
+        assert_eq!(soft::i32_to_f80(k as i32), soft::i64_to_f80(k as i32 as i64), "{} -> f80", k as i32);
+        assert_eq!(soft::u16_to_f80(k as u16), soft::u64_to_f80(k as u16 as u64), "{} -> f80", k as u16);
+        assert_eq!(soft::i16_to_f80(k as i16), soft::i64_to_f80(k as i16 as i64), "{} -> f80", k as i16);
+        assert_eq!(soft::u8_to_f80(k as u8), soft::u64_to_f80(k as u8 as u64), "{} -> f80", k as u8);
+        assert_eq!(soft::i8_to_f80(k as i8), soft::i64_to_f80(k as i8 as i64), "{} -> f80", k as i8);
+    }
+    for &f in &[
+        0.0, -0.0, 0.5, -0.99, 1.0, -1.0, 1.5, 127.9, -128.0, -128.5, 255.9, 256.0, 32767.5, -32769.0,
+        65535.9, 1e10, -1e10, 4294967295.5, 1e19, -1e19, 9223372036854775807.0, 1e30, -1e30, 1.7e38, -1.7e38,
+        3.4e38, -3.4e38, 1e300, -1e300, 5e-324, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -f64::NAN,
+    ][..]
+    {
+        let g = f64_to_f80(f.to_bits());
+        assert_eq!(soft::f80_to_u8(g), f as u8, "{:?} -> u8", f);
+        assert_eq!(soft::f80_to_u16(g), f as u16, "{:?} -> u16", f);
+        assert_eq!(soft::f80_to_u32(g), f as u32, "{:?} -> u32", f);
+        assert_eq!(soft::f80_to_u64(g), f as u64, "{:?} -> u64", f);
+        assert_eq!(soft::f80_to_u128(g), f as u128, "{:?} -> u128", f);
+        assert_eq!(soft::f80_to_i8(g), f as i8, "{:?} -> i8", f);
+        assert_eq!(soft::f80_to_i16(g), f as i16, "{:?} -> i16", f);
+        assert_eq!(soft::f80_to_i32(g), f as i32, "{:?} -> i32", f);
+        assert_eq!(soft::f80_to_i64(g), f as i64, "{:?} -> i64", f);
+        assert_eq!(soft::f80_to_i128(g), f as i128, "{:?} -> i128", f);
+        if f.fract() == 0.0 && f.abs() < 1e30 && f != 0.0 {
+            assert_eq!(soft::i128_to_f80(f as i128), g, "{} -> f80", f as i128);
+        }
+    }
+    // Invalid encodings are treated as NaN.
+    for &f in &[(1 << 62, 16400), (0, 32767), (1 << 62, 32767), (0, 65535)][..] {
+        assert_eq!(soft::f80_to_u64(f), 0, "{:?} -> u64", f);
+        assert_eq!(soft::f80_to_i64(f), 0, "{:?} -> i64", f);
+        assert_eq!(soft::f80_to_u128(f), 0, "{:?} -> u128", f);
+        assert_eq!(soft::f80_to_i8(f), 0, "{:?} -> i8", f);
+    }
+    assert_eq!(soft::f80_to_u64((!0, 16446)), u64::MAX); // Largest value below 2^64.
+    assert_eq!(soft::f80_to_i64((!0, 16445)), i64::MAX); // Largest value below 2^63.
+    assert_eq!(soft::f80_to_i64((1 << 63, 16446 | 1 << 15)), i64::MIN); // -2^63
+    assert_eq!(soft::f80_to_i128((!0, 16509 | 1 << 15)), -((u64::MAX as i128) << 63));
+}
+
+/// A simple pseudo-random number generator, to test with many different bit patterns.
+fn random_u64s(n: usize) -> impl Iterator<Item = u64> {
+    let mut x = 0x2545_F491_4F6C_DD1Du64;
+    (0..n).map(move |_| {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    })
+}
+
+#[test]
+fn test_f32_to_f64() {
+    let mut inputs = vec![
+        0, 1, 2, 0x7F_FFFF, 0x80_0000, 0x3F80_0000, 0x7F7F_FFFF, 0x7F80_0000, 0x7F80_0001, 0x7FC0_0000, 0x7FFF_FFFF,
+    ];
+    inputs.extend(random_u64s(100000).map(|x| (x >> 32) as u32));
+    inputs.extend(random_u64s(100000).map(|x| (x >> 41) as u32)); // Subnormals.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 31] {
+            let f = f32::from_bits(i);
+            assert_eq!(soft::f32_to_f64(i), (f as f64).to_bits(), "{:?} -> f64", f);
+        }
+    }
+}
+
+#[test]
+#[ignore]
+fn test_all_f32_to_f64() {
+    for i in 0..=u32::MAX {
+        let f = f32::from_bits(i);
+        assert_eq!(soft::f32_to_f64(i), (f as f64).to_bits(), "{:?} -> f64", f);
+    }
+}
+
+#[test]
+fn test_f64_to_f32() {
+    let mut inputs = vec![
+        0,
+        1,
+        (1.5f64 * 2f64.powi(-150)).to_bits(), // Rounds up to the smallest subnormal.
+        2f64.powi(-150).to_bits(), // Tie, round to even (zero)
+        2f64.powi(-150).to_bits() + 1,
+        (3.0 * 2f64.powi(-150)).to_bits(), // Tie, round to even (up)
+        (f32::MIN_POSITIVE as f64).to_bits() - 1, // Rounds up to the smallest normal.
+        (f32::MIN_POSITIVE as f64).to_bits(),
+        (f32::MAX as f64).to_bits(),
+        (f32::MAX as f64).to_bits() + (1 << 28), // Tie, round to even (infinity)
+        (f32::MAX as f64).to_bits() + (1 << 28) - 1,
+        2f64.powi(128).to_bits(),
+        1.0f64.to_bits() + (1 << 28), // Tie, round to even (down)
+        1.0f64.to_bits() + (3 << 28), // Tie, round to even (up)
+        1.0f64.to_bits() + (1 << 28) + 1, // Round to closest (up)
+        f64::MAX.to_bits(),
+        f64::INFINITY.to_bits(),
+        f64::NAN.to_bits(),
+        f64::INFINITY.to_bits() + 1, // Signalling NaN, payload lost.
+        f64::INFINITY.to_bits() + (1 << 40), // Signalling NaN, payload kept.
+    ];
+    inputs.extend(random_u64s(100000));
+    inputs.extend(random_u64s(100000).map(|x| x >> 3 & !(3 << 61) | 7 << 59)); // Exponent near the subnormal range.
+    inputs.extend(random_u64s(100000).map(|x| x >> 3 | 1 << 62)); // Exponent near the overflow range.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 63] {
+            let f = f64::from_bits(i);
+            assert_eq!(soft::f64_to_f32(i), (f as f32).to_bits(), "{:?} -> f32", f);
+        }
+    }
+}
+
+/// Checks the results of a narrowing conversion with directed rounding.
+///
+/// `a` is the magnitude of the input, `t`, `c` and `f` are the results of
+/// the `_truncate`, `_ceil` and `_floor` variants, `s` is the sign bit of the
+/// narrow format, and `widen` converts the narrow format back to the wide format.
+fn check_narrowing(a: u128, neg: bool, (t, c, f): (u128, u128, u128), s: u128, widen: impl Fn(u128) -> u128) {
+    assert_eq!(t & s != 0, neg, "sign of {:x}", a);
+    let m = t & !s;
+    let w = widen(m);
+    assert!(w <= a, "{:x} truncated to {:x}, which is too large", a, m);
+    let exact = w == a;
+    if !exact {
+        assert!(widen(m + 1) > a, "{:x} truncated to {:x}, which is too small", a, m);
+    }
+    let up = if exact { m } else { m + 1 };
+    assert_eq!(c, if neg { t } else { up | t & s }, "{:x} rounded up", a);
+    assert_eq!(f, if neg { up | s } else { t }, "{:x} rounded down", a);
+}
+
+#[test]
+fn test_narrowing_directed() {
+    let mut inputs = vec![
+        0,
+        1,
+        2f64.powi(-150).to_bits(),
+        2f64.powi(-149).to_bits() - 1,
+        2f64.powi(-149).to_bits(),
+        (f32::MIN_POSITIVE as f64).to_bits() - 1,
+        (f32::MIN_POSITIVE as f64).to_bits(),
+        (f32::MAX as f64).to_bits(),
+        (f32::MAX as f64).to_bits() + 1,
+        2f64.powi(128).to_bits(),
+        1.0f64.to_bits() + 1,
+        f64::MAX.to_bits(),
+        f64::INFINITY.to_bits(),
+    ];
+    inputs.extend(random_u64s(100000));
+    inputs.extend(random_u64s(100000).map(|x| x >> 3 & !(3 << 61) | 7 << 59)); // Exponent near the subnormal range.
+    inputs.extend(random_u64s(100000).map(|x| x >> 3 | 1 << 62)); // Exponent near the overflow range.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 63] {
+            let x = f64::from_bits(i);
+            let r = (soft::f64_to_f32_truncate(i), soft::f64_to_f32_ceil(i), soft::f64_to_f32_floor(i));
+            assert_eq!(fast::f64_to_f32_truncate(x).to_bits(), r.0, "{:?} -> f32", x);
+            assert_eq!(fast::f64_to_f32_ceil(x).to_bits(), r.1, "{:?} -> f32", x);
+            assert_eq!(fast::f64_to_f32_floor(x).to_bits(), r.2, "{:?} -> f32", x);
+            if x.is_nan() {
+                assert_eq!(r, (soft::f64_to_f32(i), soft::f64_to_f32(i), soft::f64_to_f32(i)), "{:?} -> f32", x);
+                continue;
+            }
+            let r = (r.0.into(), r.1.into(), r.2.into());
+            check_narrowing((i << 1 >> 1).into(), x.is_sign_negative(), r, 1 << 31, |f| soft::f32_to_f64(f as u32).into());
+
+            // Test f128 -> f64 using the same values, but extended with some more bits.
+            let g = f64_to_f128(i) | (i as u128).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 68;
+            let r = (soft::f128_to_f64_truncate(g), soft::f128_to_f64_ceil(g), soft::f128_to_f64_floor(g));
+            assert_eq!(fast::f128_to_f64_truncate(g).to_bits(), r.0);
+            assert_eq!(fast::f128_to_f64_ceil(g).to_bits(), r.1);
+            assert_eq!(fast::f128_to_f64_floor(g).to_bits(), r.2);
+            if g << 1 >> 1 <= 32767 << 112 {
+                let r = (r.0.into(), r.1.into(), r.2.into());
+                check_narrowing(g << 1 >> 1, x.is_sign_negative(), r, 1 << 63, |f| f64_to_f128(f as u64));
+            }
+
+            // Test f32 -> f16 using the nearest f32 values.
+            let y = soft::f64_to_f32(i);
+            if f32::from_bits(y).is_nan() {
+                continue;
+            }
+            let r = (soft::f32_to_f16_truncate(y), soft::f32_to_f16_ceil(y), soft::f32_to_f16_floor(y));
+            assert_eq!(fast::f32_to_f16_truncate(f32::from_bits(y)), r.0);
+            assert_eq!(fast::f32_to_f16_ceil(f32::from_bits(y)), r.1);
+            assert_eq!(fast::f32_to_f16_floor(f32::from_bits(y)), r.2);
+            let r = (r.0.into(), r.1.into(), r.2.into());
+            check_narrowing((y << 1 >> 1).into(), x.is_sign_negative(), r, 1 << 15, |f| (f16_to_f64(f as u16) as f32).to_bits().into());
+        }
+    }
+    for &i in &[0x7FC0_0000u32, 0x7F80_0001, 0xFFA0_0000] {
+        let f = f32_to_f16_ref_nan(i);
+        assert_eq!(soft::f32_to_f16_truncate(i), f);
+        assert_eq!(soft::f32_to_f16_ceil(i), f);
+        assert_eq!(soft::f32_to_f16_floor(i), f);
+        let g = f64_to_f128(soft::f32_to_f64(i));
+        let f = soft::f32_to_f64(i);
+        assert_eq!(soft::f128_to_f64_truncate(g), f);
+        assert_eq!(soft::f128_to_f64_ceil(g), f);
+        assert_eq!(soft::f128_to_f64_floor(g), f);
+    }
+}
+
+/// The f16 NaN that an f32 NaN is expected to be converted to.
+fn f32_to_f16_ref_nan(f: u32) -> u16 {
+    (f >> 16) as u16 & 0x8000 | 0x7E00 | (f >> 13) as u16 & 0x3FF
+}
+
+#[test]
+fn test_f16_float() {
+    for i in 0..=u16::MAX {
+        let x = f16_to_f64(i);
+        let (f, g) = (soft::f16_to_f32(i), soft::f16_to_f64(i));
+        if x.is_nan() {
+            assert_eq!(f, (x as f32).to_bits() | (i as u32 & 0x3FF) << 13, "{:x} -> f32", i);
+            assert_eq!(g, x.to_bits() | (i as u64 & 0x3FF) << 42, "{:x} -> f64", i);
+            assert_eq!(soft::f32_to_f16(f), i | 1 << 9);
+            assert_eq!(soft::f64_to_f16(g), i | 1 << 9);
+        } else {
+            assert_eq!(f, (x as f32).to_bits(), "{:x} -> f32", i);
+            assert_eq!(g, x.to_bits(), "{:x} -> f64", i);
+            assert_eq!(soft::f32_to_f16(f), i);
+            assert_eq!(soft::f64_to_f16(g), i);
+        }
+        assert_eq!(fast::f16_to_f32(i).to_bits(), f, "{:x} -> f32", i);
+        assert_eq!(fast::f16_to_f64(i).to_bits(), g, "{:x} -> f64", i);
+    }
+
+    let mut inputs = vec![
+        0,
+        1,
+        2f64.powi(-25).to_bits(), // Tie, round to even (zero)
+        2f64.powi(-25).to_bits() + 1,
+        (3.0 * 2f64.powi(-25)).to_bits(), // Tie, round to even (up)
+        (65504.0f64).to_bits(), // f16 max
+        (65520.0f64).to_bits(), // Tie, round to even (infinity)
+        (65520.0f64).to_bits() - 1,
+        (1.0 + 2f64.powi(-11) + 2f64.powi(-40)).to_bits(), // Would round to 1.0 if first rounded to f32.
+        f64::MAX.to_bits(),
+        f64::INFINITY.to_bits(),
+        f64::NAN.to_bits(),
+        f64::INFINITY.to_bits() + (1 << 45), // Signalling NaN, payload kept.
+    ];
+    inputs.extend(random_u64s(100000));
+    inputs.extend(random_u64s(100000).map(|x| x >> 6 | 0x3E4 << 52)); // Exponent near the f16 range.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 63] {
+            let x = f64::from_bits(i);
+
+            // Round to odd first, which avoids double rounding.
+            let t = soft::f64_to_f32_truncate(i);
+            let t = if soft::f32_to_f64(t) == i || x.is_nan() { t } else { t | 1 };
+            let r = soft::f64_to_f16(i);
+            assert_eq!(r, soft::f32_to_f16(t), "{:?} -> f16", x);
+            assert_eq!(fast::f64_to_f16(x), r, "{:?} -> f16", x);
+
+            // Check f32 -> f16 using the nearest f32 value, against the two candidates.
+            let y = soft::f64_to_f32(i);
+            let r = soft::f32_to_f16(y);
+            assert_eq!(fast::f32_to_f16(f32::from_bits(y)), r, "{:?} -> f16", f32::from_bits(y));
+            let v = f32::from_bits(y).abs() as f64;
+            if v.is_nan() {
+                assert_eq!(r, f32_to_f16_ref_nan(y));
+                continue;
+            }
+            let t = soft::f32_to_f16_truncate(y) & 0x7FFF;
+            let lo = f16_to_f64(t);
+            let hi = if t == 0x7BFF { 65536.0 } else { f16_to_f64(t + 1) };
+            let expected = if v - lo < hi - v || v - lo == hi - v && t & 1 == 0 { t } else { t + 1 };
+            assert_eq!(r & 0x7FFF, expected.min(0x7C00), "{:?} -> f16", v);
+            assert_eq!(r >> 15, (y >> 31) as u16);
+        }
+    }
+}
+
+/// Decodes the bits of an E4M3 value into an (exactly equal) f64.
+///
+/// Also decodes 0x7F and 0xFF as ±480, as if they weren't NaN.
+fn f8e4m3_to_f64(f: u8) -> f64 {
+    let e = (f >> 3 & 15) as i32;
+    let m = (f & 7) as f64;
+    let v = if e == 0 { m * 2f64.powi(-9) } else { (m + 8.0) * 2f64.powi(e - 10) };
+    if f >> 7 == 1 { -v } else { v }
+}
+
+/// Decodes the bits of an E5M2 value into an (exactly equal) f64.
+fn f8e5m2_to_f64(f: u8) -> f64 {
+    f16_to_f64((f as u16) << 8)
+}
+
+/// Reference rounding of a non-negative non-NaN value to an FP8 format,
+/// given the values of all codes from zero up to the largest finite value,
+/// followed by the value of the next code if it were a finite number.
+///
+/// Returns `None` on overflow.
+fn round_to_f8(v: f64, values: &[f64]) -> Option<u8> {
+    let t = values.partition_point(|&x| x <= v) - 1;
+    let r = if t + 1 == values.len() {
+        t
+    } else {
+        let mid = (values[t] + values[t + 1]) / 2.0; // Exact, as FP8 has so few bits.
+        if v < mid || v == mid && t % 2 == 0 { t } else { t + 1 }
+    };
+    if r + 1 == values.len() { None } else { Some(r as u8) }
+}
+
+#[test]
+fn test_f8() {
+    let e4m3: Vec<f64> = (0..=0x7F).map(f8e4m3_to_f64).collect();
+    let e5m2: Vec<f64> = (0..0x7C).map(f8e5m2_to_f64).chain([65536.0]).collect();
+
+    for i in 0..=u8::MAX {
+        let f = f8e4m3_to_f64(i);
+        let f = if i & 0x7F == 0x7F { f64::NAN.copysign(f) } else { f };
+        assert_eq!(soft::f8e4m3_to_f32(i), (f as f32).to_bits(), "{:x} -> f32", i);
+        assert_eq!(soft::f8e4m3_to_f64(i), f.to_bits(), "{:x} -> f64", i);
+        assert_eq!(soft::f32_to_f8e4m3(soft::f8e4m3_to_f32(i)), i);
+        assert_eq!(soft::f64_to_f8e4m3_saturate(soft::f8e4m3_to_f64(i)), i);
+        assert_eq!(soft::f8e4m3_to_u8(i), f as u8, "{:?} -> u8", f);
+        assert_eq!(soft::f8e4m3_to_u16(i), f as u16, "{:?} -> u16", f);
+        assert_eq!(soft::f8e4m3_to_u32(i), f as u32, "{:?} -> u32", f);
+        assert_eq!(soft::f8e4m3_to_u64(i), f as u64, "{:?} -> u64", f);
+        assert_eq!(soft::f8e4m3_to_u128(i), f as u128, "{:?} -> u128", f);
+        assert_eq!(soft::f8e4m3_to_i8(i), f as i8, "{:?} -> i8", f);
+        assert_eq!(soft::f8e4m3_to_i16(i), f as i16, "{:?} -> i16", f);
+        assert_eq!(soft::f8e4m3_to_i32(i), f as i32, "{:?} -> i32", f);
+        assert_eq!(soft::f8e4m3_to_i64(i), f as i64, "{:?} -> i64", f);
+        assert_eq!(soft::f8e4m3_to_i128(i), f as i128, "{:?} -> i128", f);
+
+        let f = f8e5m2_to_f64(i);
+        assert_eq!(soft::f8e5m2_to_f32(i), soft::f16_to_f32((i as u16) << 8), "{:x} -> f32", i);
+        assert_eq!(soft::f8e5m2_to_f64(i), soft::f16_to_f64((i as u16) << 8), "{:x} -> f64", i);
+        if !f.is_nan() {
+            assert_eq!(soft::f32_to_f8e5m2(soft::f8e5m2_to_f32(i)), i);
+            assert_eq!(soft::f64_to_f8e5m2(soft::f8e5m2_to_f64(i)), i);
+        }
+        assert_eq!(soft::f8e5m2_to_u8(i), f as u8, "{:?} -> u8", f);
+        assert_eq!(soft::f8e5m2_to_u32(i), f as u32, "{:?} -> u32", f);
+        assert_eq!(soft::f8e5m2_to_i16(i), f as i16, "{:?} -> i16", f);
+        assert_eq!(soft::f8e5m2_to_i128(i), f as i128, "{:?} -> i128", f);
+    }
+
+    let mut inputs = vec![
+        0,
+        1,
+        2f64.powi(-10).to_bits(), // Tie, round to even (zero) in E4M3.
+        2f64.powi(-10).to_bits() + 1,
+        2f64.powi(-17).to_bits(), // Tie, round to even (zero) in E5M2.
+        2f64.powi(-17).to_bits() + 1,
+        448f64.to_bits(),
+        464f64.to_bits(), // Tie, round to even (448) in E4M3.
+        464f64.to_bits() + 1,
+        57344f64.to_bits(),
+        61440f64.to_bits(), // Tie, round to even (infinity) in E5M2.
+        61440f64.to_bits() - 1,
+        f64::MAX.to_bits(),
+        f64::INFINITY.to_bits(),
+        f64::NAN.to_bits(),
+    ];
+    inputs.extend(random_u64s(100000));
+    inputs.extend(random_u64s(100000).map(|x| x >> 7 | 0x3EE << 52)); // Exponent near the FP8 range.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 63] {
+            let x = f64::from_bits(i);
+            let y = soft::f64_to_f32(i);
+            for (v, e4m3_nosat, e4m3_sat, e5m2_nosat, e5m2_sat) in [
+                (x, soft::f64_to_f8e4m3(i), soft::f64_to_f8e4m3_saturate(i), soft::f64_to_f8e5m2(i), soft::f64_to_f8e5m2_saturate(i)),
+                (f32::from_bits(y).into(), soft::f32_to_f8e4m3(y), soft::f32_to_f8e4m3_saturate(y), soft::f32_to_f8e5m2(y), soft::f32_to_f8e5m2_saturate(y)),
+            ] {
+                let s = (v.is_sign_negative() as u8) << 7;
+                if v.is_nan() {
+                    assert_eq!((e4m3_nosat, e4m3_sat), (0x7F | s, 0x7F | s), "{:?} -> e4m3", v);
+                    assert_eq!(e5m2_nosat & 0x7E, 0x7E, "{:?} -> e5m2", v);
+                    assert_eq!(e5m2_sat, e5m2_nosat, "{:?} -> e5m2", v);
+                    continue;
+                }
+                let r = round_to_f8(v.abs(), &e4m3);
+                assert_eq!(e4m3_nosat, r.unwrap_or(0x7F) | s, "{:?} -> e4m3", v);
+                assert_eq!(e4m3_sat, r.unwrap_or(0x7E) | s, "{:?} -> e4m3", v);
+                let r = round_to_f8(v.abs(), &e5m2);
+                assert_eq!(e5m2_nosat, r.unwrap_or(0x7C) | s, "{:?} -> e5m2", v);
+                assert_eq!(e5m2_sat, r.unwrap_or(0x7B) | s, "{:?} -> e5m2", v);
+            }
+        }
+    }
+
+    for i in 0..=u16::MAX {
+        let f = f32::from(i).to_bits();
+        assert_eq!(soft::u16_to_f8e4m3(i), soft::f32_to_f8e4m3(f), "{} -> e4m3", i);
+        assert_eq!(soft::u16_to_f8e4m3_saturate(i), soft::f32_to_f8e4m3_saturate(f), "{} -> e4m3", i);
+        assert_eq!(soft::u16_to_f8e5m2(i), soft::f32_to_f8e5m2(f), "{} -> e5m2", i);
+        assert_eq!(soft::u16_to_f8e5m2_saturate(i), soft::f32_to_f8e5m2_saturate(f), "{} -> e5m2", i);
+        let j = i as i16;
+        let f = f32::from(j).to_bits();
+        assert_eq!(soft::i16_to_f8e4m3(j), soft::f32_to_f8e4m3(f), "{} -> e4m3", j);
+        assert_eq!(soft::i16_to_f8e4m3_saturate(j), soft::f32_to_f8e4m3_saturate(f), "{} -> e4m3", j);
+        assert_eq!(soft::i16_to_f8e5m2(j), soft::f32_to_f8e5m2(f), "{} -> e5m2", j);
+        assert_eq!(soft::i16_to_f8e5m2_saturate(j), soft::f32_to_f8e5m2_saturate(f), "{} -> e5m2", j);
+        if i <= 255 {
+            assert_eq!(soft::u8_to_f8e4m3(i as u8), soft::u16_to_f8e4m3(i), "{} -> e4m3", i);
+            assert_eq!(soft::i8_to_f8e5m2_saturate(i as i8), soft::i16_to_f8e5m2_saturate(i as i8 as i16), "{} -> e5m2", i as i8);
+        }
+    }
+    for &i in &[0, 1, 448, 464, 465, 65535, 65536, 1 << 31, u32::MAX as u128, u64::MAX as u128, u128::MAX] {
+        let r = soft::u16_to_f8e4m3(i.min(65535) as u16);
+        assert_eq!(soft::u128_to_f8e4m3(i), r, "{} -> e4m3", i);
+        assert_eq!(soft::u64_to_f8e4m3(i as u64), soft::u128_to_f8e4m3((i as u64).into()), "{} -> e4m3", i as u64);
+        assert_eq!(soft::u32_to_f8e4m3(i as u32), soft::u128_to_f8e4m3((i as u32).into()), "{} -> e4m3", i as u32);
+        let j = (i >> 1) as i128;
+        assert_eq!(soft::i128_to_f8e4m3_saturate(-j), soft::u128_to_f8e4m3_saturate(j as u128) | ((j != 0) as u8) << 7, "{} -> e4m3", -j);
+        assert_eq!(soft::i64_to_f8e5m2(i as i64), soft::i128_to_f8e5m2(i as i64 as i128), "{} -> e5m2", i as i64);
+        assert_eq!(soft::i32_to_f8e5m2_saturate(i as i32), soft::i128_to_f8e5m2_saturate(i as i32 as i128), "{} -> e5m2", i as i32);
+    }
+    assert_eq!(soft::u128_to_f8e4m3_saturate(u128::MAX), 0x7E);
+    assert_eq!(soft::i128_to_f8e4m3(i128::MIN), 0xFF);
+    assert_eq!(soft::u64_to_f8e5m2(u64::MAX), 0x7C);
+    assert_eq!(soft::i64_to_f8e5m2_saturate(i64::MIN), 0xFB);
+}
+
+/// Decodes an unsigned minifloat without infinity or NaN into an (exactly equal) f64.
+fn minifloat_to_f64(f: u8, m_bits: u32, bias: i32) -> f64 {
+    let e = (f >> m_bits) as i32;
+    let m = (f & !(!0 << m_bits)) as f64;
+    if e == 0 {
+        m * 2f64.powi(1 - bias - m_bits as i32)
+    } else {
+        (m + 2f64.powi(m_bits as i32)) * 2f64.powi(e - bias - m_bits as i32)
+    }
+}
+
+#[test]
+fn test_mx() {
+    type Encode<T> = fn(&[f32; 32]) -> (u8, [T; 32]);
+    type Decode<T> = fn(u8, &[T; 32]) -> [f32; 32];
+
+    // The values of all codes up to the largest one, plus the next.
+    let e4m3: Vec<f64> = (0..=0x7F).map(f8e4m3_to_f64).collect();
+    let e5m2: Vec<f64> = (0..0x7C).map(f8e5m2_to_f64).chain([65536.0]).collect();
+    let e3m2: Vec<f64> = (0..=0x20).map(|f| minifloat_to_f64(f, 2, 3)).collect();
+    let e2m3: Vec<f64> = (0..=0x20).map(|f| minifloat_to_f64(f, 3, 1)).collect();
+    let e2m1: Vec<f64> = (0..=0x8).map(|f| minifloat_to_f64(f, 1, 1)).collect();
+    let formats = [
+        (mx::f32_to_mxfp8_e4m3 as Encode<u8>, mx::mxfp8_e4m3_to_f32 as Decode<u8>, 8, &e4m3[..], 7),
+        (mx::f32_to_mxfp8_e5m2, mx::mxfp8_e5m2_to_f32, 15, &e5m2, 7),
+        (mx::f32_to_mxfp6_e3m2, mx::mxfp6_e3m2_to_f32, 4, &e3m2, 5),
+        (mx::f32_to_mxfp6_e2m3, mx::mxfp6_e2m3_to_f32, 2, &e2m3, 5),
+        (mx::f32_to_mxfp4, mx::mxfp4_to_f32, 2, &e2m1, 3),
+    ];
+    let int8: Vec<f64> = (0..=129).map(|i| i as f64 / 64.0).collect();
+
+    let mut blocks = vec![
+        [0.0; 32],
+        [-0.0; 32],
+        [f32::from_bits(1); 32],
+        [f32::MAX; 32],
+        [1.0, 1.5, 2.0, 3.0, 4.0, 6.0, -0.5, 0.25, 5.0, 5.5, 7.0, 0.0, 0.125, 0.1, -6.5, 1.25, 0.75, 0.3, 3.5, -2.5, 2.25, 8.0, 1e-3, 9.0, 4.5, -4.5, 0.0625, 0.5625, 0.4, 0.6, 0.7, 0.8],
+    ];
+    let mut random = random_u64s(1 << 20);
+    for _ in 0..2000 {
+        let center = (random.next().unwrap() % 256) as u32;
+        let mut block = [0.0; 32];
+        for x in &mut block {
+            let r = random.next().unwrap();
+            let e = center.saturating_sub((r >> 32) as u32 % 24).min(254);
+            *x = f32::from_bits((r as u32) & 0x807F_FFFF | e << 23);
+        }
+        blocks.push(block);
+    }
+
+    for block in &blocks {
+        let max = block.iter().map(|x| x.abs() as f64).fold(0.0, f64::max);
+        let log2 = if max == 0.0 { -1000 } else { max.log2().floor() as i32 };
+        for &(encode, decode, emax, values, sign) in &formats {
+            let x = (log2 - emax).max(-127);
+            let (scale, p) = encode(block);
+            assert_eq!(scale as i32, x + 127, "{:?}", block);
+            let d = decode(scale, &p);
+            for i in 0..32 {
+                let v = block[i].abs() as f64 * 2f64.powi(-x);
+                let r = round_to_f8(v, values).unwrap_or(values.len() as u8 - 2);
+                let s = (block[i].is_sign_negative() as u8) << sign;
+                assert_eq!(p[i], r | s, "{:?} in {:?}", block[i], block);
+                let expected = values[r as usize] * 2f64.powi(x);
+                let expected = if s != 0 { -expected } else { expected };
+                assert_eq!(d[i].to_bits(), (expected as f32).to_bits(), "{:?} in {:?}", block[i], block);
+            }
+        }
+
+        let x = log2.max(-127);
+        let (scale, p) = mx::f32_to_mxint8(block);
+        assert_eq!(scale as i32, x + 127, "{:?}", block);
+        let d = mx::mxint8_to_f32(scale, &p);
+        for i in 0..32 {
+            let v = block[i].abs() as f64 * 2f64.powi(-x);
+            let r = round_to_f8(v, &int8).unwrap();
+            let expected = if block[i].is_sign_negative() { -(r as i32) } else { r.min(127) as i32 };
+            assert_eq!(p[i] as i32, expected, "{:?} in {:?}", block[i], block);
+            let expected = expected as f64 / 64.0 * 2f64.powi(x);
+            assert_eq!(d[i], expected as f32, "{:?} in {:?}", block[i], block);
+        }
+    }
+
+    let mut block = [1.0; 32];
+    block[7] = f32::NAN;
+    assert_eq!(mx::f32_to_mxfp4(&block), (255, [0; 32]));
+    block[7] = f32::NEG_INFINITY;
+    assert_eq!(mx::f32_to_mxint8(&block), (255, [0; 32]));
+    assert!(mx::mxfp8_e4m3_to_f32(255, &[0; 32]).iter().all(|x| x.is_nan()));
+    assert!(mx::mxfp8_e4m3_to_f32(127, &[0x7F; 32]).iter().all(|x| x.is_nan()));
+    assert_eq!(mx::mxfp8_e5m2_to_f32(127, &[0x7C; 32]), [f32::INFINITY; 32]);
+    assert_eq!(mx::mxfp4_to_f32(254, &[0x7; 32]), [f32::INFINITY; 32]); // 6 * 2^127 overflows.
+    assert_eq!(mx::mxint8_to_f32(0, &[1; 32]), [f32::from_bits(1 << 16); 32]); // 2^-6 * 2^-127 is subnormal.
+}
+
+#[test]
+#[allow(clippy::unusual_byte_groupings)] // Grouped as sign, exponent and mantissa.
+fn test_minifloat() {
+    use minifloat::*;
+
+    // Compare with the specialized implementations.
+    let mut inputs = vec![0, 1, f64::MAX.to_bits(), f64::INFINITY.to_bits(), f64::NAN.to_bits(), f64::INFINITY.to_bits() + (1 << 45)];
+    inputs.extend(random_u64s(100000));
+    inputs.extend(random_u64s(100000).map(|x| x >> 6 | 0x3E4 << 52)); // Exponent near the small formats' range.
+    inputs.extend(random_u64s(100000).map(|x| x >> 4 | 0x380 << 52)); // Exponent near the f32 range.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 63] {
+            let f = soft::f64_to_f32(i);
+            assert_eq!(from_f64::<F32>(i), f.into(), "{:x}", i);
+            assert_eq!(from_f64::<F16>(i), soft::f64_to_f16(i).into(), "{:x}", i);
+            assert_eq!(from_f32::<F16>(f), soft::f32_to_f16(f).into(), "{:x}", f);
+            assert_eq!(from_f32::<Bf16>(f), soft::f32_to_bf16(f).into(), "{:x}", f);
+            assert_eq!(from_f64::<F8E4M3>(i), soft::f64_to_f8e4m3(i).into(), "{:x}", i);
+            assert_eq!(from_f64::<F8E5M2>(i), soft::f64_to_f8e5m2(i).into(), "{:x}", i);
+            assert_eq!(from_f32::<F8E4M3>(f), soft::f32_to_f8e4m3(f).into(), "{:x}", f);
+            assert_eq!(to_f64::<F32>(f.into()), soft::f32_to_f64(f), "{:x}", f);
+            assert_eq!(to_f32::<F64>(i), f, "{:x}", i);
+            assert_eq!(convert::<F64, F64>(i), if f64::from_bits(i).is_nan() { i | 1 << 51 } else { i });
+            assert_eq!(to_u32::<F32>(f.into()), soft::f32_to_u32(f), "{:x}", f);
+            assert_eq!(to_i16::<F32>(f.into()), soft::f32_to_i16(f), "{:x}", f);
+            assert_eq!(to_u128::<F64>(i), soft::f64_to_u128(i), "{:x}", i);
+            assert_eq!(to_i128::<F64>(i), soft::f64_to_i128(i), "{:x}", i);
+            assert_eq!(to_i64::<F64>(i), soft::f64_to_i64(i), "{:x}", i);
+            assert_eq!(to_u8::<F64>(i), soft::f64_to_u8(i), "{:x}", i);
+            let x = (i as u128 * 0x9E37_79B9_7F4A_7C15) >> (i % 128);
+            assert_eq!(from_u128::<F32>(x), soft::u128_to_f32(x).into(), "{}", x);
+            assert_eq!(from_u128::<F64>(x), soft::u128_to_f64(x), "{}", x);
+            assert_eq!(from_i128::<F64>(x as i128), soft::i128_to_f64(x as i128), "{}", x as i128);
+            assert_eq!(from_u64::<F32>(i), soft::u64_to_f32(i).into(), "{}", i);
+            assert_eq!(from_i32::<F16>(i as i32), soft::i32_to_f16(i as i32).into(), "{}", i as i32);
+            assert_eq!(from_u16::<F8E5M2>(i as u16), soft::u16_to_f8e5m2(i as u16).into(), "{}", i as u16);
+            assert_eq!(from_i8::<F8E4M3>(i as i8), soft::i8_to_f8e4m3(i as i8).into(), "{}", i as i8);
+        }
+    }
+    for i in 0..=u16::MAX {
+        assert_eq!(to_f32::<F16>(i.into()), soft::f16_to_f32(i), "{:x}", i);
+        assert_eq!(to_f64::<F16>(i.into()), soft::f16_to_f64(i), "{:x}", i);
+        let quiet = if i & 0x7FFF > 0x7F80 { 1 << 22 } else { 0 }; // soft::bf16_to_f32 doesn't quiet NaN.
+        assert_eq!(to_f32::<Bf16>(i.into()), soft::bf16_to_f32(i) | quiet, "{:x}", i);
+        assert_eq!(to_i32::<F16>(i.into()), soft::f16_to_i32(i), "{:x}", i);
+        assert_eq!(to_u8::<F16>(i.into()), soft::f16_to_u8(i), "{:x}", i);
+        assert_eq!(to_i128::<Bf16>(i.into()), soft::bf16_to_i128(i), "{:x}", i);
+        assert_eq!(to_u16::<Bf16>(i.into()), soft::bf16_to_u16(i), "{:x}", i);
+        if i <= 255 {
+            let j = i as u8;
+            assert_eq!(to_f32::<F8E4M3>(i.into()), soft::f8e4m3_to_f32(j), "{:x}", i);
+            assert_eq!(to_f64::<F8E5M2>(i.into()), soft::f8e5m2_to_f64(j), "{:x}", i);
+            assert_eq!(to_i8::<F8E4M3>(i.into()), soft::f8e4m3_to_i8(j), "{:x}", i);
+        }
+    }
+
+    // A format with a different bias, and without subnormals.
+    struct Custom;
+    impl Format for Custom {
+        const EXPONENT_BITS: u32 = 3;
+        const MANTISSA_BITS: u32 = 2;
+        const BIAS: i32 = 0;
+        const INFINITY: bool = false;
+        const NAN: bool = false;
+        const SUBNORMALS: bool = false;
+    }
+    assert_eq!(from_u8::<Custom>(1), 0); // No subnormals.
+    assert_eq!(from_u8::<Custom>(2), 0b0_001_00);
+    assert_eq!(from_u8::<Custom>(200), 0b0_111_10);
+    assert_eq!(from_u8::<Custom>(255), 0b0_111_11); // Saturates.
+    assert_eq!(from_i8::<Custom>(-3), 0b1_001_10);
+    assert_eq!(from_f64::<Custom>(1.6f64.to_bits()), 0); // No subnormals.
+    assert_eq!(from_f64::<Custom>(1.9f64.to_bits()), 0b0_001_00); // Rounds up to the smallest normal.
+    assert_eq!(from_f64::<Custom>(f64::NAN.to_bits()), 0);
+    assert_eq!(from_f64::<Custom>(f64::NEG_INFINITY.to_bits()), 0b1_111_11);
+    assert_eq!(to_f64::<Custom>(0b0_000_11), 0);
+    assert_eq!(to_f64::<Custom>(0b1_111_11), (-224.0f64).to_bits());
+    assert_eq!(to_u8::<Custom>(0b0_111_11), 224);
+    assert_eq!(to_i8::<Custom>(0b1_111_11), -128);
+
+    // The OCP FP4 and FP6 formats.
+    for i in 0..16 {
+        assert_eq!(to_f64::<F4E2M1>(i), (minifloat_to_f64(i as u8 & 7, 1, 1) * if i >= 8 { -1.0 } else { 1.0 }).to_bits());
+        assert_eq!(from_f64::<F4E2M1>(to_f64::<F4E2M1>(i)), i);
+    }
+    for i in 0..64 {
+        assert_eq!(from_f64::<F6E3M2>(to_f64::<F6E3M2>(i)), i);
+        assert_eq!(from_f64::<F6E2M3>(to_f64::<F6E2M3>(i)), i);
+    }
+    assert_eq!(from_f32::<F4E2M1>(5.0f32.to_bits()), 0b0_11_0); // Tie, round to even (4)
+    assert_eq!(from_f32::<F4E2M1>(100.0f32.to_bits()), 0b0_11_1); // Saturates to 6.
+    assert_eq!(from_f32::<F4E2M1>((-0.25f32).to_bits()), 0b1_00_0); // Tie, round to even (-0)
+    assert_eq!(from_f32::<Tf32>(1.0f32.to_bits() + (1 << 12)), 127 << 10); // Tie, round to even (down)
+}
+
+#[test]
+fn test_tf32_fp24() {
+    use minifloat::{Fp24, Tf32};
+
+    let mut inputs: Vec<u32> = vec![0, 1, 0x1000, 0x1001, 0x3000, 0x7F7F_FFFF, 0x7F80_0000, 0x7F80_0001, 0x7FC0_0000];
+    inputs.extend(random_u64s(100000).map(|x| x as u32));
+    inputs.extend(random_u64s(100000).map(|x| x as u32 >> 4 | 3 << 28)); // Exponent near the FP24 range.
+    for &i in &inputs {
+        for &i in &[i, i | 1 << 31] {
+            let f = f32::from_bits(i);
+            let r = soft::f32_to_tf32(i);
+            assert_eq!(r, (minifloat::from_f32::<Tf32>(i) as u32) << 13, "{:?} -> tf32", f);
+            let t = soft::f32_to_tf32_truncate(i);
+            if f.is_nan() {
+                assert_eq!(t, r, "{:?} -> tf32", f);
+            } else {
+                assert_eq!(t, i & !0x1FFF, "{:?} -> tf32", f);
+            }
+
+            let r = soft::f32_to_fp24(i);
+            assert_eq!(r, minifloat::from_f32::<Fp24>(i) as u32, "{:?} -> fp24", f);
+            assert_eq!(soft::fp24_to_f32(r), minifloat::to_f32::<Fp24>(r.into()), "{:x} -> f32", r);
+            let t = soft::f32_to_fp24_truncate(i);
+            if f.is_nan() {
+                assert_eq!(t, r, "{:?} -> fp24", f);
+                continue;
+            }
+            assert_eq!(t >> 23, i >> 31, "{:?} -> fp24", f);
+            let a = f.abs();
+            let lo = f32::from_bits(soft::fp24_to_f32(t & !(1 << 23)));
+            let hi = f32::from_bits(soft::fp24_to_f32((t & !(1 << 23)) + 1));
+            if f.is_infinite() {
+                assert_eq!(lo, a, "{:?} -> fp24", f);
+            } else {
+                assert!(lo <= a && (a < hi || hi.is_infinite()), "{:?} -> fp24 {:x}", f, t);
+            }
+        }
+    }
+
+    for i in 0..=u16::MAX {
+        assert_eq!(soft::u16_to_tf32(i), (minifloat::from_u16::<Tf32>(i) as u32) << 13, "{} -> tf32", i);
+        assert_eq!(soft::i16_to_tf32(i as i16), (minifloat::from_i16::<Tf32>(i as i16) as u32) << 13, "{} -> tf32", i as i16);
+        if i <= 255 {
+            assert_eq!(soft::u8_to_tf32(i as u8), soft::u16_to_tf32(i), "{} -> tf32", i);
+            assert_eq!(soft::i8_to_tf32(i as i8), soft::i16_to_tf32(i as i8 as i16), "{} -> tf32", i as i8);
+        }
+    }
+    for x in random_u64s(100000) {
+        let y = (x as u128).wrapping_mul(0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835) >> (x % 128);
+        assert_eq!(soft::u128_to_tf32(y), (minifloat::from_u128::<Tf32>(y) as u32) << 13, "{} -> tf32", y);
+        assert_eq!(soft::i128_to_tf32(y as i128), (minifloat::from_i128::<Tf32>(y as i128) as u32) << 13, "{} -> tf32", y as i128);
+        let y = x >> (x % 64);
+        assert_eq!(soft::u64_to_tf32(y), (minifloat::from_u64::<Tf32>(y) as u32) << 13, "{} -> tf32", y);
+        assert_eq!(soft::i64_to_tf32(y as i64), (minifloat::from_i64::<Tf32>(y as i64) as u32) << 13, "{} -> tf32", y as i64);
+        let y = y as u32;
+        assert_eq!(soft::u32_to_tf32(y), (minifloat::from_u32::<Tf32>(y) as u32) << 13, "{} -> tf32", y);
+        assert_eq!(soft::i32_to_tf32(y as i32), (minifloat::from_i32::<Tf32>(y as i32) as u32) << 13, "{} -> tf32", y as i32);
+    }
+    assert_eq!(soft::u128_to_tf32(u128::MAX), f32::INFINITY.to_bits()); // Overflows, like u128_to_f32_round.
+    assert_eq!(soft::i128_to_tf32(i128::MIN), 0xFF00_0000);
+    assert_eq!(soft::u32_to_tf32(0x0FFF_FFFF), 0x4D80_0000); // Rounds up to 2^28.
+}
+
+/// Decodes the bits of an IBM HFP single precision number into an (exactly equal) f64.
+fn hfp32_to_f64_ref(h: u32) -> f64 {
+    let f = (h & 0xFF_FFFF) as f64;
+    let v = f * 2f64.powi(4 * (h >> 24 & 0x7F) as i32 - 256 - 24);
+    if h >> 31 == 1 { -v } else { v }
+}
+
+/// Reference implementation of integer to IBM HFP conversion, with a fraction of `bits` bits.
+fn u128_to_hfp_ref(x: u128, bits: u32, truncate: bool) -> u64 {
+    if x == 0 {
+        return 0;
+    }
+    let d = (128 - x.leading_zeros()).div_ceil(4); // Number of hexadecimal digits.
+    let (mut f, mut e) = if 4 * d <= bits {
+        ((x << (bits - 4 * d)) as u64, 64 + d as u64)
+    } else {
+        let s = 4 * d - bits;
+        let f = (x >> s) as u64;
+        let r = x & ((1 << s) - 1);
+        let half = 1 << (s - 1);
+        let up = !truncate && (r > half || r == half && f & 1 == 1);
+        (f + up as u64, 64 + d as u64)
+    };
+    if f >> bits != 0 {
+        f >>= 4;
+        e += 1;
+    }
+    if e > 127 { !0 >> (57 - bits) } else { e << bits | f }
+}
+
+#[test]
+fn test_hfp() {
+    // The examples from the IBM System/360 Principles of Operation.
+    assert_eq!(soft::f64_to_hfp32(1.0f64.to_bits()), 0x4110_0000);
+    assert_eq!(soft::f64_to_hfp32((-118.625f64).to_bits()), 0xC276_A000);
+    assert_eq!(soft::hfp32_to_f32(0xC276_A000), (-118.625f32).to_bits());
+    assert_eq!(soft::hfp64_to_f64(0x4110_0000_0000_0000), 1.0f64.to_bits());
+    assert_eq!(soft::f64_to_hfp32(0.1f64.to_bits()), 0x4019_999A);
+    assert_eq!(soft::f64_to_hfp32_truncate(0.1f64.to_bits()), 0x4019_9999);
+    assert_eq!(soft::f64_to_hfp64(0.1f64.to_bits()), 0x4019_9999_9999_999A);
+    assert_eq!(soft::f64_to_hfp32(f64::INFINITY.to_bits()), 0x7FFF_FFFF);
+    assert_eq!(soft::f64_to_hfp64(f64::NEG_INFINITY.to_bits()), !0);
+    assert_eq!(soft::f64_to_hfp32(f64::NAN.to_bits()), 0);
+    assert_eq!(soft::f64_to_hfp32(1e300f64.to_bits()), 0x7FFF_FFFF);
+    assert_eq!(soft::f64_to_hfp64(2f64.powi(-320).to_bits()), 0); // Too small, even unnormalized.
+    assert_eq!(soft::f64_to_hfp64(2f64.powi(-270).to_bits()), 0x0000_0400_0000_0000); // Unnormalized.
+    assert_eq!(soft::hfp32_to_f32(0x7FFF_FFFF), f32::INFINITY.to_bits());
+    assert_eq!(soft::hfp32_to_f32_truncate(0x7FFF_FFFF), f32::MAX.to_bits());
+    assert_eq!(soft::hfp32_to_f64(0x0000_0001), 2f64.powi(-280).to_bits());
+    assert_eq!(soft::hfp32_to_f32(0x0000_0001), 0);
+    assert_eq!(soft::hfp32_to_f64(0x8000_0000), (-0.0f64).to_bits());
+    assert_eq!(soft::hfp32_to_f64(0x4200_0000), 0); // Unnormalized zero.
+
+    for h in random_u64s(100000) {
+        // HFP to IEEE.
+        let g = (h >> 32) as u32;
+        let v = hfp32_to_f64_ref(g);
+        assert_eq!(soft::hfp32_to_f64(g), v.to_bits(), "{:x}", g);
+        assert_eq!(soft::hfp32_to_f32(g), (v as f32).to_bits(), "{:x}", g);
+        assert_eq!(soft::hfp32_to_f32_truncate(g), soft::f64_to_f32_truncate(v.to_bits()), "{:x}", g);
+        assert_eq!(soft::hfp32_to_i32(g), v as i32, "{:x}", g);
+        assert_eq!(soft::hfp32_to_u64(g), v as u64, "{:x}", g);
+        assert_eq!(soft::hfp32_to_i128(g), v as i128, "{:x}", g);
+        assert_eq!(soft::hfp32_to_u8(g), v as u8, "{:x}", g);
+
+        let f = h & !0 >> 8;
+        let k = 4 * (h >> 56 & 0x7F) as i32 - 256 - 56;
+        let s = if h >> 63 == 1 { -1.0 } else { 1.0 };
+        let v = s * f64::from_bits(soft::u64_to_f64(f)) * 2f64.powi(k);
+        assert_eq!(soft::hfp64_to_f64(h), v.to_bits(), "{:x}", h);
+        let n = f.leading_zeros().min(11);
+        let v = s * (f >> (11 - n) << (11 - n)) as f64 * 2f64.powi(k);
+        assert_eq!(soft::hfp64_to_f64_truncate(h), v.to_bits(), "{:x}", h);
+        assert_eq!(soft::hfp64_to_f32_truncate(h), soft::f64_to_f32_truncate(v.to_bits()), "{:x}", h);
+        if (-100..100).contains(&(k + 56 - f.leading_zeros() as i32)) {
+            let v = s * f32::from_bits(soft::u64_to_f32(f)) as f64 * 2f64.powi(k);
+            assert_eq!(soft::hfp64_to_f32(h), (v as f32).to_bits(), "{:x}", h);
+        }
+        let h = h & !7; // Exact in f64.
+        let v = f64::from_bits(soft::hfp64_to_f64(h));
+        assert_eq!(soft::hfp64_to_i64(h), v as i64, "{:x}", h);
+        assert_eq!(soft::hfp64_to_u128(h), v as u128, "{:x}", h);
+        assert_eq!(soft::hfp64_to_i16(h), v as i16, "{:x}", h);
+        assert_eq!(soft::hfp64_to_u32(h), v as u32, "{:x}", h);
+
+        // IEEE to HFP.
+        for x in [f64::from_bits(h), f64::from_bits(h >> 2 | 0x3000 << 48), f64::from_bits(h >> 4 | 0x3800 << 48)] {
+            if x.is_nan() || x.is_infinite() {
+                continue;
+            }
+            let a = x.abs();
+            let t = soft::f64_to_hfp32_truncate(x.to_bits());
+            let r = soft::f64_to_hfp32(x.to_bits());
+            assert_eq!(t >> 31, x.is_sign_negative() as u32, "{:?}", x);
+            assert_eq!(r >> 31, x.is_sign_negative() as u32, "{:?}", x);
+            let (t, r) = (t & !0 >> 1, r & !0 >> 1);
+            let lo = hfp32_to_f64_ref(t);
+            if t == 0x7FFF_FFFF {
+                assert!(a >= lo, "{:?}", x);
+                assert_eq!(r, t, "{:?}", x);
+                continue;
+            }
+            let next = if t & 0xFF_FFFF == 0xFF_FFFF { ((t & !0xFF_FFFF) + (1 << 24)) | 0x10_0000 } else { t + 1 };
+            let hi = hfp32_to_f64_ref(next);
+            assert!(lo <= a && a < hi, "{:?} -> {:x}", x, t);
+            let mid = (lo + hi) / 2.0;
+            let expected = if a < mid || a == mid && t & 1 == 0 { t } else { next };
+            assert_eq!(r, expected, "{:?}", x);
+            if (-250.0..250.0).contains(&a.log2()) {
+                assert_eq!(soft::hfp64_to_f64(soft::f64_to_hfp64(x.to_bits())), x.to_bits(), "{:?}", x);
+                assert_eq!(soft::f64_to_hfp64_truncate(x.to_bits()), soft::f64_to_hfp64(x.to_bits()), "{:?}", x);
+                assert_ne!(soft::f64_to_hfp64(x.to_bits()) >> 52 & 15, 0, "{:?}", x); // Normalized.
+            }
+        }
+        let y = soft::f64_to_f32(h);
+        assert_eq!(soft::f32_to_hfp32(y), soft::f64_to_hfp32(soft::f32_to_f64(y)));
+        assert_eq!(soft::f32_to_hfp32_truncate(y), soft::f64_to_hfp32_truncate(soft::f32_to_f64(y)));
+        assert_eq!(soft::f32_to_hfp64(y), soft::f64_to_hfp64(soft::f32_to_f64(y)));
+
+        // Integers to HFP.
+        let x = (h as u128).wrapping_mul(0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835) >> (h % 128);
+        assert_eq!(soft::u128_to_hfp32(x) as u64, u128_to_hfp_ref(x, 24, false), "{}", x);
+        assert_eq!(soft::u128_to_hfp32_truncate(x) as u64, u128_to_hfp_ref(x, 24, true), "{}", x);
+        assert_eq!(soft::u128_to_hfp64(x), u128_to_hfp_ref(x, 56, false), "{}", x);
+        assert_eq!(soft::u128_to_hfp64_truncate(x), u128_to_hfp_ref(x, 56, true), "{}", x);
+        let j = (x >> 1) as i128;
+        assert_eq!(soft::i128_to_hfp64(-j), soft::u128_to_hfp64(j as u128) | ((j != 0) as u64) << 63, "{}", -j);
+        assert_eq!(soft::i128_to_hfp32_truncate(-j), soft::u128_to_hfp32_truncate(j as u128) | ((j != 0) as u32) << 31, "{}", -j);
+        let x = x as u64;
+        assert_eq!(soft::u64_to_hfp32(x) as u64, u128_to_hfp_ref(x.into(), 24, false), "{}", x);
+        assert_eq!(soft::u64_to_hfp64_truncate(x), u128_to_hfp_ref(x.into(), 56, true), "{}", x);
+        assert_eq!(soft::i64_to_hfp64(x as i64), soft::i128_to_hfp64(x as i64 as i128), "{}", x as i64);
+        let x = x as u32;
+        assert_eq!(soft::u32_to_hfp32_truncate(x) as u64, u128_to_hfp_ref(x.into(), 24, true), "{}", x);
+        assert_eq!(soft::u32_to_hfp64(x), u128_to_hfp_ref(x.into(), 56, false), "{}", x);
+        assert_eq!(soft::i32_to_hfp32(x as i32), soft::i128_to_hfp32(x as i32 as i128), "{}", x as i32);
+        assert_eq!(soft::i32_to_hfp64(x as i32), soft::i128_to_hfp64(x as i32 as i128), "{}", x as i32);
+        let x = x as u16;
+        assert_eq!(soft::u16_to_hfp32(x) as u64, u128_to_hfp_ref(x.into(), 24, false), "{}", x);
+        assert_eq!(soft::i16_to_hfp64(x as i16), soft::i128_to_hfp64(x as i16 as i128), "{}", x as i16);
+        assert_eq!(soft::u8_to_hfp64(x as u8), u128_to_hfp_ref((x as u8).into(), 56, false), "{}", x as u8);
+        assert_eq!(soft::i8_to_hfp32(x as i8), soft::i128_to_hfp32(x as i8 as i128), "{}", x as i8);
+    }
+}
+
+#[test]
+fn test_vax() {
+    // Known values, in memory order.
+    assert_eq!(soft::f32_to_vaxf(1.0f32.to_bits()), 0x0000_4080);
+    assert_eq!(soft::f32_to_vaxf(std::f32::consts::PI.to_bits()), 0x0FDB_4149);
+    assert_eq!(soft::f64_to_vaxd(1.0f64.to_bits()), 0x4080);
+    assert_eq!(soft::f64_to_vaxg(1.0f64.to_bits()), 0x4010);
+    assert_eq!(soft::f64_to_vaxg((-0.5f64).to_bits()), 0xC000);
+    assert_eq!(soft::vaxf_to_f32(0x0FDB_4149), std::f32::consts::PI.to_bits());
+    assert_eq!(soft::vaxd_to_f64(0x4080), 1.0f64.to_bits());
+    assert_eq!(soft::vaxg_to_f32(0xC010), (-1.0f32).to_bits());
+
+    // Word order.
+    let l = 0x4123_4567_89AB_CDEF;
+    assert_eq!(soft::vaxd_to_f64(0xCDEF_89AB_4567_4123), minifloat::convert::<minifloat::VaxD, minifloat::F64>(l));
+    assert_eq!(soft::vaxg_to_f64(0xCDEF_89AB_4567_4123), minifloat::convert::<minifloat::VaxG, minifloat::F64>(l));
+    assert_eq!(soft::f64_to_vaxg(minifloat::convert::<minifloat::VaxG, minifloat::F64>(l)), 0xCDEF_89AB_4567_4123);
+
+    // Zero, reserved operands and NaN.
+    assert_eq!(soft::vaxf_to_f32(0), 0);
+    assert_eq!(soft::vaxf_to_f32(0x1234_0000), 0); // Dirty zero.
+    assert!(f32::from_bits(soft::vaxf_to_f32(0x8000)).is_nan());
+    assert!(f64::from_bits(soft::vaxd_to_f64(0x8000)).is_nan());
+    assert!(f64::from_bits(soft::vaxg_to_f64(0x1234_8000)).is_nan());
+    assert_eq!(soft::vaxf_to_i32(0x8000), 0);
+    assert_eq!(soft::vaxg_to_u64(0x8000), 0);
+    assert_eq!(soft::f32_to_vaxf((-0.0f32).to_bits()), 0);
+    assert_eq!(soft::f32_to_vaxf(f32::NAN.to_bits()), 0x8000);
+    assert_eq!(soft::f64_to_vaxd(f64::NAN.to_bits()), 0x8000);
+    assert_eq!(soft::f64_to_vaxg((-f64::NAN).to_bits()), 0x8000);
+
+    // Overflow and underflow.
+    assert_eq!(soft::f32_to_vaxf(f32::INFINITY.to_bits()), 0xFFFF_7FFF);
+    assert_eq!(soft::f32_to_vaxf(f32::MIN.to_bits()), 0xFFFF_FFFF);
+    assert_eq!(soft::f64_to_vaxd(1e300f64.to_bits()), 0xFFFF_FFFF_FFFF_7FFF);
+    assert_eq!(soft::f64_to_vaxg(f64::MAX.to_bits()), 0xFFFF_FFFF_FFFF_7FFF);
+    assert_eq!(soft::f64_to_vaxf(1e-50f64.to_bits()), 0);
+    assert_eq!(soft::f64_to_vaxf((-1e-50f64).to_bits()), 0);
+
+    // Negative zero is not a reserved operand in the generic conversions either.
+    assert_eq!(minifloat::from_f64::<minifloat::VaxF>((-0.0f64).to_bits()), 0);
+    assert_eq!(minifloat::from_f64::<minifloat::VaxF>((-1e-50f64).to_bits()), 0);
+    assert_eq!(minifloat::from_f32::<minifloat::VaxG>((-0.0f32).to_bits()), 0);
+    assert_eq!(minifloat::convert::<minifloat::F8E4M3, minifloat::VaxD>(0x80), 0);
+    assert_eq!(minifloat::from_f64::<minifloat::F8E4M3>((-0.0f64).to_bits()), 0x80);
+    assert_eq!(soft::vaxf_to_f32(0xFFFF_7FFF), 0x7EFF_FFFF);
+    assert_eq!(soft::vaxg_to_f32(0x7FF0), f32::INFINITY.to_bits());
+    assert_eq!(soft::vaxd_to_f32(0x0080), 0x0020_0000); // 2^-128 is subnormal in f32.
+    assert_eq!(soft::u128_to_vaxf(u128::MAX), 0xFFFF_7FFF);
+    assert_eq!(soft::vaxf_to_i8(0xFFFF_7FFF), i8::MAX);
+    assert_eq!(soft::vaxf_to_u64(0xFFFF_FFFF), 0);
+
+    for i in 0..20000u64 {
+        let h = i.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let f = f64::from_bits(h);
+        let y = f32::from_bits(h as u32);
+
+        // Round trips.
+        if y.is_normal() && y.abs() < 1e38 {
+            assert_eq!(soft::vaxf_to_f32(soft::f32_to_vaxf(y.to_bits())), y.to_bits(), "{:?}", y);
+            assert_eq!(soft::vaxd_to_f32(soft::f32_to_vaxd(y.to_bits())), y.to_bits(), "{:?}", y);
+        }
+        if f.is_normal() && (-1020.0..1020.0).contains(&f.abs().log2()) {
+            assert_eq!(soft::vaxg_to_f64(soft::f64_to_vaxg(f.to_bits())), f.to_bits(), "{:?}", f);
+        }
+
+        // Word order and value.
+        let l = (h as u32).rotate_left(16);
+        let e = (l >> 23 & 0xFF) as i32;
+        if e != 0 {
+            let v = (1 << 23 | l & 0x7F_FFFF) as f64 * 2f64.powi(e - 129 - 23);
+            assert_eq!(soft::vaxf_to_f64(h as u32), (if l >> 31 == 1 { -v } else { v }).to_bits(), "{:x}", h as u32);
+        }
+        let l = (h as u16 as u64) << 48 | (h >> 16 & 0xFFFF) << 32 | (h >> 32 & 0xFFFF) << 16 | h >> 48;
+        let e = (l >> 55 & 0xFF) as i32;
+        if e != 0 {
+            let v = (1 << 55 | l & !0 >> 9) as f64 * 2f64.powi(e - 129 - 55);
+            assert_eq!(soft::vaxd_to_f64(h), (if l >> 63 == 1 { -v } else { v }).to_bits(), "{:x}", h);
+        }
+        let e = (l >> 52 & 0x7FF) as i32;
+        if e > 2 {
+            let v = (1 << 52 | l & !0 >> 12) as f64 / 2f64.powi(52) * 2f64.powi(e - 1025);
+            assert_eq!(soft::vaxg_to_f64(h), (if l >> 63 == 1 { -v } else { v }).to_bits(), "{:x}", h);
+        }
+
+        // Integers.
+        let x = h >> (h % 64);
+        assert_eq!(soft::u64_to_vaxg(x), soft::f64_to_vaxg(soft::u64_to_f64(x)), "{}", x);
+        assert_eq!(soft::i64_to_vaxd(-(x as i64 >> 11)), soft::f64_to_vaxd((-(x as i64 >> 11) as f64).to_bits()), "{}", x as i64 >> 11);
+        assert_eq!(soft::vaxd_to_i64(soft::i64_to_vaxd(x as i64 >> 8)), x as i64 >> 8, "{}", x as i64 >> 8); // Exact.
+        assert_eq!(soft::u32_to_vaxf(x as u32), soft::f32_to_vaxf(soft::u32_to_f32(x as u32)), "{}", x as u32);
+        assert_eq!(soft::i16_to_vaxf(x as i16), soft::f32_to_vaxf((x as i16 as f32).to_bits()), "{}", x as i16);
+        assert_eq!(soft::vaxf_to_i32(soft::f32_to_vaxf(y.to_bits())), soft::f32_to_i32(soft::vaxf_to_f32(soft::f32_to_vaxf(y.to_bits()))), "{:?}", y);
+        assert_eq!(soft::vaxg_to_i64(soft::f64_to_vaxg(f.to_bits())), soft::f64_to_i64(soft::vaxg_to_f64(soft::f64_to_vaxg(f.to_bits()))), "{:?}", f);
+    }
+}
+
+/// Moves the sign bit of an MBF number with `bits` mantissa bits to the top,
+/// where `minifloat` expects it. Zero becomes positive zero.
+fn mbf_to_logical(x: u64, bits: u32) -> u64 {
+    if x >> (bits + 1) == 0 { return 0; }
+    (x >> bits & 1) << (bits + 8) | x >> (bits + 1) << bits | x & !(!0 << bits)
+}
+
+/// Replaces negative zero by positive zero, in a format with an 8-bit
+/// exponent and `bits` mantissa bits.
+fn positive_zero(x: u64, bits: u32) -> u64 {
+    if x >> bits & 0xFF == 0 { 0 } else { x }
+}
+
+#[test]
+fn test_mbf() {
+    use minifloat::{Format, VaxD, VaxF};
+
+    // Same as VAX F_floating and D_floating, apart from the bit order.
+    struct Mbf40;
+    impl Format for Mbf40 {
+        const EXPONENT_BITS: u32 = 8;
+        const MANTISSA_BITS: u32 = 31;
+        const BIAS: i32 = 129;
+        const INFINITY: bool = false;
+        const NAN: bool = false;
+        const SUBNORMALS: bool = false;
+    }
+
+    // Known values.
+    assert_eq!(soft::f32_to_mbf32(1.0f32.to_bits()), 0x8100_0000);
+    assert_eq!(soft::f32_to_mbf32((-1.0f32).to_bits()), 0x8180_0000);
+    assert_eq!(soft::f32_to_mbf32(0.5f32.to_bits()), 0x8000_0000);
+    assert_eq!(soft::f32_to_mbf32(10.0f32.to_bits()), 0x8420_0000);
+    assert_eq!(soft::f64_to_mbf40(1.0f64.to_bits()), 0x81_0000_0000);
+    assert_eq!(soft::f64_to_mbf64((-3.0f64).to_bits()), 0x82C0_0000_0000_0000);
+    assert_eq!(soft::mbf32_to_f32(0x8420_0000), 10.0f32.to_bits());
+    assert_eq!(soft::mbf64_to_f64(0x8100_0000_0000_0000), 1.0f64.to_bits());
+    assert_eq!(soft::u16_to_mbf32(1000), 0x8A7A_0000);
+    assert_eq!(soft::i32_to_mbf64(-1000), 0x8AFA_0000_0000_0000);
+    assert_eq!(soft::mbf40_to_i16(0x8A_FA00_0000), -1000);
+
+    // Zero.
+    assert_eq!(soft::f32_to_mbf32((-0.0f32).to_bits()), 0);
+    assert_eq!(soft::mbf32_to_f32(0x0012_3456), 0);
+    assert_eq!(soft::mbf64_to_f64(0x00FF_FFFF_FFFF_FFFF), 0);
+    assert_eq!(soft::mbf32_to_i32(0x00FF_FFFF), 0);
+    assert_eq!(soft::mbf64_to_mbf32(0x0080_0000_0000_0001), 0);
+
+    // Overflow and underflow.
+    assert_eq!(soft::f32_to_mbf32(f32::MAX.to_bits()), 0xFF7F_FFFF);
+    assert_eq!(soft::f32_to_mbf32(f32::NEG_INFINITY.to_bits()), 0xFFFF_FFFF);
+    assert_eq!(soft::f32_to_mbf32(f32::NAN.to_bits()), 0);
+    assert_eq!(soft::f32_to_mbf32_checked(f32::MAX.to_bits()), None);
+    assert_eq!(soft::f32_to_mbf32_checked(f32::INFINITY.to_bits()), None);
+    assert_eq!(soft::f32_to_mbf32_checked(f32::NAN.to_bits()), None);
+    assert_eq!(soft::f32_to_mbf32_checked(1.7e38f32.to_bits()), Some(0xFF7F_C99E));
+    assert_eq!(soft::f64_to_mbf64_checked(1e-300f64.to_bits()), Some(0));
+    assert_eq!(soft::f64_to_mbf40_checked((-1e39f64).to_bits()), None);
+    assert_eq!(soft::f64_to_mbf40((-1e39f64).to_bits()), 0xFF_FFFF_FFFF);
+    assert_eq!(soft::mbf64_to_mbf32_checked(0xFF7F_FFFF_FFFF_FFFF), None);
+    assert_eq!(soft::mbf64_to_mbf32(0xFF7F_FFFF_FFFF_FFFF), 0xFF7F_FFFF);
+    assert_eq!(soft::mbf64_to_mbf40_checked(0xFF7F_FFFF_7FFF_FFFF), Some(0xFF_7FFF_FF80));
+    assert_eq!(soft::mbf64_to_f32(0xFF7F_FFFF_FFFF_FFFF), 0x7F00_0000); // Not infinity.
+    assert_eq!(soft::mbf64_to_f32(0x0100_0000_0000_0000), 0x0020_0000); // Subnormal.
+    assert_eq!(soft::u128_to_mbf32(u128::MAX), 0xFF7F_FFFF);
+    assert_eq!(soft::mbf64_to_u128(0xFF7F_FFFF_FFFF_FFFF), ((1 << 56) - 1) << 71);
+    assert_eq!(soft::mbf32_to_i64(0xFFFF_FFFF), i64::MIN);
+
+    for h in random_u64s(100000) {
+        let f = f64::from_bits(h);
+
+        // MBF to IEEE.
+        for (x, bits) in [(h >> 32, 23), (h >> 24, 31), (h, 55)] {
+            let e = (x >> (bits + 1)) as i32;
+            if e == 0 { continue; }
+            let v = (1 << bits | x & !(!0 << bits)) as f64 * 2f64.powi(e - 129 - bits); // Rounds once, for mbf64.
+            let v = if x >> bits & 1 != 0 { -v } else { v };
+            let (a, b) = match bits {
+                23 => (soft::mbf32_to_f64(x as u32), soft::mbf32_to_f32(x as u32)),
+                31 => (soft::mbf40_to_f64(x), soft::mbf40_to_f32(x)),
+                _ => (soft::mbf64_to_f64(x), soft::mbf64_to_f32(x)),
+            };
+            assert_eq!(a, v.to_bits(), "{:x}", x);
+            if bits < 55 {
+                assert_eq!(b, soft::f64_to_f32(a), "{:x}", x);
+            } else {
+                assert_eq!(b as u64, minifloat::convert::<VaxD, minifloat::F32>(mbf_to_logical(x, 55)), "{:x}", x);
+            }
+        }
+        let x32 = (h >> 32) as u32;
+        let x40 = h >> 24;
+        assert_eq!(soft::f64_to_mbf32(soft::mbf32_to_f64(x32)), if x32 >> 24 == 0 { 0 } else { x32 }, "{:x}", x32);
+        assert_eq!(soft::f64_to_mbf40(soft::mbf40_to_f64(x40)), if x40 >> 32 == 0 { 0 } else { x40 }, "{:x}", x40);
+
+        // IEEE to MBF.
+        let logical = [
+            mbf_to_logical(soft::f64_to_mbf32(h).into(), 23),
+            mbf_to_logical(soft::f64_to_mbf40(h), 31),
+            mbf_to_logical(soft::f64_to_mbf64(h), 55),
+        ];
+        let reference = [minifloat::from_f64::<VaxF>(h), minifloat::from_f64::<Mbf40>(h), minifloat::from_f64::<VaxD>(h)];
+        for ((&a, &r), bits) in logical.iter().zip(&reference).zip([23, 31, 55]) {
+            assert_eq!(a, positive_zero(r, bits), "{:?}", f);
+        }
+        let max = soft::mbf64_to_f64(0xFF7F_FFFF_FFFF_FFFF);
+        match soft::f64_to_mbf64_checked(h) {
+            Some(x) => assert_eq!(x, soft::f64_to_mbf64(h), "{:?}", f),
+            None => assert!(f.is_nan() || f.abs() > f64::from_bits(max), "{:?}", f),
+        }
+        let y = soft::f64_to_f32(h);
+        assert_eq!(soft::f32_to_mbf32(y), soft::f64_to_mbf32(soft::f32_to_f64(y)), "{:?}", y);
+        assert_eq!(soft::f32_to_mbf40_checked(y), soft::f64_to_mbf40_checked(soft::f32_to_f64(y)), "{:?}", y);
+
+        // MBF to MBF.
+        assert_eq!(mbf_to_logical(soft::mbf64_to_mbf32(h).into(), 23), positive_zero(minifloat::convert::<VaxD, VaxF>(mbf_to_logical(h, 55)), 23), "{:x}", h);
+        assert_eq!(mbf_to_logical(soft::mbf64_to_mbf40(h), 31), positive_zero(minifloat::convert::<VaxD, Mbf40>(mbf_to_logical(h, 55)), 31), "{:x}", h);
+        assert_eq!(soft::mbf40_to_mbf32(x40), soft::f64_to_mbf32(soft::mbf40_to_f64(x40)), "{:x}", x40);
+        assert_eq!(soft::mbf32_to_mbf64(x32), soft::f64_to_mbf64(soft::mbf32_to_f64(x32)), "{:x}", x32);
+        assert_eq!(soft::mbf64_to_mbf40(soft::mbf40_to_mbf64(x40)), soft::mbf40_to_mbf64(x40) >> 24 & 0xFF_FFFF_FFFF, "{:x}", x40);
+
+        // Integers.
+        let x = (h as u128).wrapping_mul(0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835) >> (h % 128);
+        assert_eq!(mbf_to_logical(soft::u128_to_mbf64(x), 55), minifloat::from_u128::<VaxD>(x), "{}", x);
+        assert_eq!(mbf_to_logical(soft::i128_to_mbf40(x as i128), 31), minifloat::from_i128::<Mbf40>(x as i128), "{}", x as i128);
+        assert_eq!(mbf_to_logical(soft::u64_to_mbf32(x as u64).into(), 23), minifloat::from_u64::<VaxF>(x as u64), "{}", x as u64);
+        assert_eq!(soft::i32_to_mbf32(x as i32), soft::f64_to_mbf32((x as i32 as f64).to_bits()), "{}", x as i32);
+        assert_eq!(soft::mbf64_to_i32(soft::i32_to_mbf64(x as i32)), x as i32, "{}", x as i32); // Exact.
+        assert_eq!(soft::mbf40_to_u32(soft::u32_to_mbf40(x as u32)), x as u32, "{}", x as u32); // Exact.
+        assert_eq!(soft::mbf64_to_u128(h), minifloat::to_u128::<VaxD>(mbf_to_logical(h, 55)), "{:x}", h);
+        assert_eq!(soft::mbf64_to_i64(h), minifloat::to_i64::<VaxD>(mbf_to_logical(h, 55)), "{:x}", h);
+        assert_eq!(soft::mbf40_to_i8(x40), minifloat::to_i8::<Mbf40>(mbf_to_logical(x40, 31)), "{:x}", x40);
+        assert_eq!(soft::mbf32_to_u16(x32), minifloat::to_u16::<VaxF>(mbf_to_logical(x32.into(), 23)), "{:x}", x32);
+    }
+}
+
+/// Decodes a `decimal64` in the BID encoding into its sign, coefficient and
+/// exponent.
+fn decimal64_parts(x: u64) -> (bool, u64, i32) {
+    let (e, c) = if x >> 61 & 3 != 3 {
+        (x >> 53 & 0x3FF, x & !0 >> 11)
+    } else {
+        (x >> 51 & 0x3FF, 4 << 51 | x & !0 >> 13)
+    };
+    (x >> 63 != 0, c, e as i32 - 398)
+}
+
+/// Compares a non-negative `f64` to c × 10<sup>q</sup>, exactly.
+fn cmp_f64_decimal(f: f64, c: u128, q: i32) -> std::cmp::Ordering {
+    if f == 0.0 {
+        return if c == 0 { std::cmp::Ordering::Equal } else { std::cmp::Ordering::Less };
+    } else if f.is_infinite() || c == 0 {
+        return std::cmp::Ordering::Greater;
+    }
+    // Both as the digits without trailing zeros, and the exponent of the first digit.
+    let s = format!("{:.800e}", f);
+    let (m, e) = s.split_once('e').unwrap();
+    let a = (m.replace('.', "").trim_end_matches('0').to_string(), e.parse::<i32>().unwrap());
+    let d = c.to_string();
+    let b = (d.trim_end_matches('0').to_string(), q + d.len() as i32 - 1);
+    a.1.cmp(&b.1).then(a.0.cmp(&b.0))
+}
+
+#[test]
+fn test_decimal() {
+    use decimal::*;
+    use std::cmp::Ordering::*;
+    let modes = [Round::TiesToEven, Round::TiesToAway, Round::TowardZero, Round::TowardPositive, Round::TowardNegative];
+
+    // Known values.
+    assert_eq!(u8_to_decimal32(1), 0x3280_0001);
+    assert_eq!(i32_to_decimal64(-1), 0xB1C0_0000_0000_0001);
+    assert_eq!(u64_to_decimal128(1), 0x3040 << 112 | 1);
+    assert_eq!(u64_to_decimal64(9_999_999_999_999_999, Round::TowardZero), 0x6C73_86F2_6FC0_FFFF);
+    assert_eq!(u64_to_decimal64(9_007_199_254_740_991, Round::TowardZero), 0x31DF_FFFF_FFFF_FFFF);
+    assert_eq!(u64_to_decimal64(9_007_199_254_740_992, Round::TowardZero), 0x6C70_0000_0000_0000);
+    assert_eq!(f64_to_decimal64(1.0f64.to_bits(), Round::TiesToEven), 0x31C0_0000_0000_0001);
+    assert_eq!(f64_to_decimal64(0.5f64.to_bits(), Round::TiesToEven), 0x31A0_0000_0000_0005);
+    assert_eq!(f64_to_decimal64((-0.0f64).to_bits(), Round::TiesToEven), 0xB1C0_0000_0000_0000);
+    assert_eq!(f64_to_decimal64(f64::INFINITY.to_bits(), Round::TiesToEven), 0x7800_0000_0000_0000);
+    assert_eq!(f64_to_decimal64(f64::NAN.to_bits(), Round::TiesToEven), 0x7C00_0000_0000_0000);
+    assert_eq!(decimal64_to_f64(0x7800_0000_0000_0000, Round::TowardZero), f64::INFINITY.to_bits());
+    assert!(f64::from_bits(decimal64_to_f64(0x7E00_0000_0000_0000, Round::TiesToEven)).is_nan());
+    assert_eq!(decimal64_to_f64(0x77FB_86F2_6FC0_FFFF, Round::TiesToEven), f64::INFINITY.to_bits()); // Largest value.
+    assert_eq!(decimal64_to_f64(0x77FB_86F2_6FC0_FFFF, Round::TowardZero), f64::MAX.to_bits());
+    assert_eq!(decimal64_to_f64(0xF7FB_86F2_6FC0_FFFF, Round::TowardPositive), f64::MIN.to_bits());
+    assert_eq!(decimal64_to_f64(0x0000_0000_0000_0001, Round::TiesToEven), 0); // 10^-398
+    assert_eq!(decimal64_to_f64(0x0000_0000_0000_0001, Round::TowardPositive), 1);
+    assert_eq!(decimal64_to_f64(0x8000_0000_0000_0001, Round::TowardNegative), 1 << 63 | 1);
+    assert_eq!(decimal64_to_f64(0x6C73_86F2_6FC0_FFFF, Round::TiesToEven), 1e16f64.to_bits());
+    assert_eq!(decimal64_to_f64(0x6C7F_FFFF_FFFF_FFFF, Round::TiesToEven), 0); // Non-canonical.
+    assert_eq!(decimal64_to_i32(0x6C7F_FFFF_FFFF_FFFF, Round::TowardPositive), 0);
+
+    assert_eq!(decimal64_parts(f64_to_decimal64(1234567890123456.5f64.to_bits(), Round::TiesToEven)), (false, 1_234_567_890_123_456, 0));
+    assert_eq!(decimal64_parts(f64_to_decimal64((-1234567890123456.5f64).to_bits(), Round::TiesToAway)), (true, 1_234_567_890_123_457, 0));
+    assert_eq!(decimal64_parts(f64_to_decimal64(0.1f64.to_bits(), Round::TiesToEven)), (false, 1_000_000_000_000_000, -16));
+    assert_eq!(decimal64_parts(f64_to_decimal64(0.1f64.to_bits(), Round::TowardPositive)), (false, 1_000_000_000_000_001, -16));
+    assert_eq!(decimal64_parts(f64_to_decimal64(f64::MAX.to_bits(), Round::TiesToEven)), (false, 1_797_693_134_862_316, 293));
+    assert_eq!(decimal64_parts(f64_to_decimal64(1u64, Round::TiesToEven)), (false, 4_940_656_458_412_465, -339));
+
+    // Rounding of integers.
+    let d = i64_to_decimal64(i64::MIN, Round::TiesToEven);
+    assert_eq!(decimal64_parts(d), (true, 9_223_372_036_854_776, 3));
+    assert_eq!(decimal64_parts(i64_to_decimal64(i64::MIN, Round::TowardZero)), (true, 9_223_372_036_854_775, 3));
+    assert_eq!(decimal64_parts(i64_to_decimal64(i64::MIN, Round::TowardPositive)), (true, 9_223_372_036_854_775, 3));
+    assert_eq!(decimal64_parts(i64_to_decimal64(i64::MIN, Round::TowardNegative)), (true, 9_223_372_036_854_776, 3));
+    assert_eq!(decimal64_parts(u64_to_decimal64(99_999_999_999_999_995, Round::TiesToEven)), (false, 1_000_000_000_000_000, 2));
+    assert_eq!(decimal64_parts(u64_to_decimal64(99_999_999_999_999_995, Round::TowardZero)), (false, 9_999_999_999_999_999, 1));
+    assert_eq!(u32_to_decimal32(12_345_675, Round::TiesToEven), 0x3300_0000 | 1_234_568);
+    assert_eq!(u32_to_decimal32(12_345_665, Round::TiesToEven), 0x3300_0000 | 1_234_566);
+    assert_eq!(u32_to_decimal32(12_345_665, Round::TiesToAway), 0x3300_0000 | 1_234_567);
+    assert_eq!(i32_to_decimal32(-12_345_665, Round::TowardNegative), 0xB300_0000 | 1_234_567);
+    assert_eq!(u128_to_decimal32(u128::MAX, Round::TiesToEven), 0x4280_0000 | 3_402_824);
+    assert_eq!(u128_to_decimal128(u128::MAX, Round::TowardZero), (6181 << 113) | 3_402_823_669_209_384_634_633_746_074_317_682);
+
+    // Rounding to integers.
+    for (c, expected) in [(25, [2, 3, 2, 3, 2]), (-25, [-2, -3, -2, -2, -3]), (15, [2, 2, 1, 2, 1]), (-1, [0, 0, 0, 0, -1]), (-5, [0, -1, 0, 0, -1])] {
+        let x = 0x31A0_0000_0000_0000 | ((c < 0) as u64) << 63 | (c as i64).unsigned_abs(); // c × 10^-1
+        for (&round, &expected) in modes.iter().zip(&expected) {
+            assert_eq!(decimal64_to_i32(x, round), expected, "{} {:?}", c, round);
+            assert_eq!(decimal64_to_i128(x, round), expected as i128, "{} {:?}", c, round);
+            assert_eq!(decimal64_to_u8(x, round), expected.max(0) as u8, "{} {:?}", c, round);
+        }
+    }
+    assert_eq!(decimal64_to_u64(0x77FB_86F2_6FC0_FFFF, Round::TiesToEven), u64::MAX);
+    assert_eq!(decimal64_to_i8(0xF800_0000_0000_0000, Round::TiesToEven), i8::MIN);
+    assert_eq!(decimal64_to_i16(0x7C00_0000_0000_0000, Round::TiesToEven), 0);
+    assert_eq!(decimal128_to_u128(u128_to_decimal128(u128::MAX, Round::TiesToEven), Round::TiesToEven), 340_282_366_920_938_463_463_374_607_431_768_200_000);
+    assert_eq!(decimal128_to_i128(i128_to_decimal128(i128::MIN, Round::TowardZero), Round::TiesToEven), -170_141_183_460_469_231_731_687_303_715_884_100_000);
+    assert_eq!(decimal32_to_u32(0x3380_0000 | 123, Round::TiesToEven), 12_300);
+    assert_eq!(decimal32_to_i64(0x2E00_0000 | 1_234_567, Round::TiesToEven), 0);
+
+    for h in random_u64s(2000) {
+        // Integers to decimal64.
+        let x = h >> (h % 64);
+        for &round in &modes {
+            let (_, c, q) = decimal64_parts(u64_to_decimal64(x, round));
+            assert!(c < 10u64.pow(16) && q >= 0, "{} {:?}", x, round);
+            let v = c as u128 * 10u128.pow(q as u32);
+            let ulp = 10u128.pow(q as u32);
+            let x = x as u128;
+            match round {
+                Round::TowardZero | Round::TowardNegative => assert!(v <= x && x < v + ulp, "{} {:?}", x, round),
+                Round::TowardPositive => assert!(v >= x && x + ulp > v, "{} {:?}", x, round),
+                _ => assert!(2 * v.abs_diff(x) <= ulp, "{} {:?}", x, round),
+            }
+            if q == 0 {
+                assert_eq!(v, x);
+            }
+        }
+        let p = 10u64.pow(x.to_string().len().saturating_sub(16) as u32);
+        assert_eq!(decimal64_to_u64(u64_to_decimal64(x, Round::TowardZero), Round::TiesToEven), x / p * p, "{}", x);
+        assert_eq!(decimal64_to_i32(i32_to_decimal64(x as i32), Round::TiesToEven), x as i32);
+        assert_eq!(decimal128_to_u64(u64_to_decimal128(x), Round::TowardZero), x);
+        assert_eq!(decimal32_to_i16(i16_to_decimal32(x as i16), Round::TowardZero), x as i16);
+
+        // f64 to decimal64.
+        let f = f64::from_bits(h);
+        if f.is_finite() && f != 0.0 {
+            let a = f.abs();
+            for &round in &modes {
+                let (negative, c, q) = decimal64_parts(f64_to_decimal64(h, round));
+                assert_eq!(negative, f < 0.0);
+                let c = c as u128;
+                let away = match round {
+                    Round::TowardZero => Some(false),
+                    Round::TowardPositive => Some(!negative),
+                    Round::TowardNegative => Some(negative),
+                    _ => None,
+                };
+                if cmp_f64_decimal(a, c, q) == Equal {
+                    assert!(q == 0 || q < 0 && !c.is_multiple_of(10) || q > 0 && c >= 10u128.pow(15), "{:?} {:?}", f, round);
+                    continue;
+                }
+                assert!(c >= 10u128.pow(15) && c < 10u128.pow(16), "{:?} {:?}", f, round);
+                let lo = if c == 10u128.pow(15) { (c * 10 - 1, q - 1) } else { (c - 1, q) }; // Previous value.
+                match away {
+                    Some(false) => assert!(cmp_f64_decimal(a, c, q) == Greater && cmp_f64_decimal(a, c + 1, q) == Less, "{:?} {:?}", f, round),
+                    Some(true) => assert!(cmp_f64_decimal(a, c, q) == Less && cmp_f64_decimal(a, lo.0, lo.1) == Greater, "{:?} {:?}", f, round),
+                    None => {
+                        let hi = cmp_f64_decimal(a, (2 * c + 1) * 5, q - 1);
+                        let lo = cmp_f64_decimal(a, (2 * lo.0 + 1) * 5, lo.1 - 1);
+                        assert!(hi != Greater && lo != Less, "{:?} {:?}", f, round);
+                        if round == Round::TiesToAway {
+                            assert!(hi != Equal, "{:?} {:?}", f, round);
+                        } else if hi == Equal || lo == Equal {
+                            assert!(c.is_multiple_of(2), "{:?} {:?}", f, round);
+                        }
+                    }
+                }
+            }
+        }
+
+        // decimal64 to f64.
+        let x = h & !(3 << 61) | (h % 3) << 61; // Avoid infinity and NaN.
+        let (negative, c, q) = decimal64_parts(x);
+        let c = if c < 10u64.pow(16) { c } else { 0 };
+        let near = format!("{}e{}", c, q).parse::<f64>().unwrap();
+        let exact = cmp_f64_decimal(near, c.into(), q);
+        let down = if near.is_infinite() || exact == Greater { near.to_bits() - 1 } else { near.to_bits() };
+        let up = if exact == Less { near.to_bits() + 1 } else { near.to_bits() };
+        let sign_bit = (negative as u64) << 63;
+        assert_eq!(decimal64_to_f64(x, Round::TiesToEven), near.to_bits() | sign_bit, "{:x}", x);
+        assert_eq!(decimal64_to_f64(x, Round::TowardZero), down | sign_bit, "{:x}", x);
+        assert_eq!(decimal64_to_f64(x, Round::TowardPositive), if negative { down } else { up } | sign_bit, "{:x}", x);
+        assert_eq!(decimal64_to_f64(x, Round::TowardNegative), if negative { up } else { down } | sign_bit, "{:x}", x);
+        let away = decimal64_to_f64(x, Round::TiesToAway);
+        assert!(away == near.to_bits() | sign_bit || away == up | sign_bit && exact == Less, "{:x}", x);
+    }
+}
+
+#[test]
+fn test_dpd() {
+    use decimal::*;
+
+    // Known values.
+    assert_eq!(u8_to_dpd32(1), 0x2250_0001);
+    assert_eq!(i8_to_dpd64(-1), 0xA238_0000_0000_0001);
+    assert_eq!(u8_to_dpd128(1), 0x2208 << 112 | 1);
+    assert_eq!(u64_to_dpd64(9_999_999_999_999_999, Round::TiesToEven), 0x6E38 << 48 | (0..5).fold(0, |x, i| x | 0xFF << (10 * i)));
+    assert_eq!(u16_to_dpd32(5), 0x2250_0005);
+    for (n, declet) in [(79, 0x079), (80, 0x00A), (99, 0x05F), (555, 0x2D5), (888, 0x06E), (999, 0x0FF), (123, 0x0A3)] {
+        assert_eq!(u16_to_dpd32(n) & 0x3FF, declet, "{}", n);
+        assert_eq!(dpd32_to_u16(0x2250_0000 | declet, Round::TiesToEven), n, "{}", n);
+    }
+    assert_eq!(dpd64_to_i32(0xA238_0000_0000_0001, Round::TiesToEven), -1);
+    assert_eq!(dpd32_to_decimal32(0x7800_0000), 0x7800_0000);
+    assert_eq!(decimal64_to_dpd64(0xFC00_0000_0000_0000), 0xFC00_0000_0000_0000);
+    assert_eq!(decimal64_to_dpd64(0x7E00_0000_0000_007B), 0x7E00_0000_0000_00A3); // Signaling NaN with payload 123.
+    assert_eq!(dpd64_to_decimal64(0x7E00_0000_0000_00A3), 0x7E00_0000_0000_007B);
+    assert_eq!(decimal32_to_dpd32(0x6CB8_9680), 0x2250_0000); // Non-canonical, so zero.
+
+    // All declets.
+    for x in 0..1024 {
+        let n = dpd32_to_u32(0x2250_0000 | x, Round::TiesToEven);
+        assert!(n < 1000, "{:x}", x);
+        let canonical = x & 0x6E != 0x6E || x & 0x300 == 0;
+        assert_eq!(u32_to_dpd32(n, Round::TiesToEven) == 0x2250_0000 | x, canonical, "{:x}", x);
+    }
+
+    for h in random_u64s(10000) {
+        // Round trips.
+        let (_, c, _) = decimal64_parts(h);
+        if c < 10u64.pow(16) && h >> 59 & 0xF != 0xF {
+            assert_eq!(dpd64_to_decimal64(decimal64_to_dpd64(h)), h, "{:x}", h);
+        }
+        let canonical = (0..5).all(|i| h >> (10 * i) & 0x6E != 0x6E || h >> (10 * i) & 0x300 == 0);
+        if canonical && h >> 59 & 0xF != 0xF {
+            assert_eq!(decimal64_to_dpd64(dpd64_to_decimal64(h)), h, "{:x}", h);
+        }
+        let y = h as u32;
+        if y & 0x6000_0000 != 0x6000_0000 {
+            assert_eq!(dpd32_to_decimal32(decimal32_to_dpd32(y)), y, "{:x}", y);
+        }
+        let z = (h as u128) << 64 | h.rotate_left(17) as u128;
+        assert_eq!(decimal128_to_dpd128(dpd128_to_decimal128(decimal128_to_dpd128(z))), decimal128_to_dpd128(z), "{:x}", z);
+
+        // Integers.
+        let x = h >> (h % 64);
+        for round in [Round::TiesToEven, Round::TowardNegative] {
+            assert_eq!(u64_to_dpd64(x, round), decimal64_to_dpd64(u64_to_decimal64(x, round)), "{}", x);
+            assert_eq!(i64_to_dpd32(-(x as i64), round), decimal32_to_dpd32(i64_to_decimal32(-(x as i64), round)), "{}", x);
+            assert_eq!(u128_to_dpd128((x as u128) << 64 | x as u128, round), decimal128_to_dpd128(u128_to_decimal128((x as u128) << 64 | x as u128, round)), "{}", x);
+            assert_eq!(dpd64_to_i64(h, round), decimal64_to_i64(dpd64_to_decimal64(h), round), "{:x}", h);
+            assert_eq!(dpd128_to_u128(z, round), decimal128_to_u128(dpd128_to_decimal128(z), round), "{:x}", z);
+        }
+        assert_eq!(dpd64_to_u32(u32_to_dpd64(x as u32), Round::TowardZero), x as u32);
+        assert_eq!(dpd128_to_i64(i64_to_dpd128(x as i64), Round::TowardZero), x as i64);
+    }
+}
+
+/// Decodes the lowest `n` bits of `p` as a posit (with es = 2), bit by bit.
+fn posit_to_f64_reference(p: u64, n: u32) -> f64 {
+    if p == 0 {
+        return 0.0;
+    }
+    if p == 1 << (n - 1) {
+        return f64::NAN;
+    }
+    let negative = p >> (n - 1) != 0;
+    let a = if negative { p.wrapping_neg() } else { p } & !(!0 << (n - 1));
+    let bits: Vec<bool> = (0..n - 1).rev().map(|i| a >> i & 1 != 0).collect();
+    let run = bits.iter().take_while(|&&b| b == bits[0]).count();
+    let k = if bits[0] { run as i32 - 1 } else { -(run as i32) };
+    let rest: Vec<bool> = bits.iter().skip(run + 1).copied().collect();
+    let e = (0..2).fold(0, |e, i| e * 2 + rest.get(i).map_or(0, |&b| b as i32));
+    let f = rest.iter().skip(2).rev().fold(0.0, |f, &b| (f + b as u8 as f64) / 2.0);
+    let v = (1.0 + f) * 2f64.powi(4 * k + e);
+    if negative { -v } else { v }
+}
+
+/// Rounds a finite f64 to a posit of `n` bits, by searching for the
+/// surrounding posits and comparing against the posit of `n + 1` bits in
+/// between.
+fn f64_to_posit_reference(f: f64, n: u32) -> u64 {
+    if f == 0.0 {
+        return 0;
+    }
+    let a = f.abs();
+    let max = (1 << (n - 1)) - 1;
+    let (mut lo, mut hi) = (1u64, max); // Find the largest p with value(p) <= a.
+    if a < posit_to_f64_reference(1, n) {
+        hi = 1;
+    }
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if posit_to_f64_reference(mid, n) <= a { lo = mid } else { hi = mid - 1 }
+    }
+    let p = if lo == max || posit_to_f64_reference(lo, n) == a || a < posit_to_f64_reference(1, n) {
+        lo
+    } else {
+        let half = posit_to_f64_reference(lo << 1 | 1, n + 1);
+        if a > half || a == half && lo & 1 == 1 { lo + 1 } else { lo }
+    };
+    if f < 0.0 { p.wrapping_neg() & !(!0 << n) } else { p }
+}
+
+#[test]
+fn test_posit() {
+    // Known values.
+    assert_eq!(soft::f64_to_posit8(1f64.to_bits()), 0x40);
+    assert_eq!(soft::f64_to_posit16((-1f64).to_bits()), 0xC000);
+    assert_eq!(soft::f64_to_posit32(1.5f64.to_bits()), 0x4400_0000);
+    assert_eq!(soft::f64_to_posit8(f64::INFINITY.to_bits()), 0x80);
+    assert_eq!(soft::f32_to_posit16(f32::NAN.to_bits()), 0x8000);
+    assert_eq!(soft::f64_to_posit32(1e300f64.to_bits()), 0x7FFF_FFFF); // maxpos
+    assert_eq!(soft::f64_to_posit32((-1e-300f64).to_bits()), 0xFFFF_FFFF); // -minpos
+    assert_eq!(soft::posit8_to_f64(0x7F), 2f64.powi(24).to_bits());
+    assert_eq!(soft::posit16_to_f32(0x0001), 2f32.powi(-56).to_bits());
+    assert_eq!(soft::posit32_to_f64(0x7FFF_FFFF), 2f64.powi(120).to_bits());
+    assert!(f64::from_bits(soft::posit32_to_f64(0x8000_0000)).is_nan());
+    assert_eq!(soft::posit32_to_f64(0), 0);
+    assert_eq!(soft::u128_to_posit32(u128::MAX), 0x7FFF_FFFF); // 2^128 is more than maxpos (2^120).
+    assert_eq!(soft::i8_to_posit8(-128), soft::f64_to_posit8((-128f64).to_bits()));
+    assert_eq!(soft::posit32_to_u64(0x7FFF_FFFF), u64::MAX);
+    assert_eq!(soft::posit32_to_i128(0xFFFF_FFFF), 0);
+    assert_eq!(soft::posit16_to_i32(0x8000), 0); // NaR
+    assert_eq!(soft::posit8_to_i8(soft::f64_to_posit8((-2.5f64).to_bits())), -2);
+    assert_eq!(soft::posit8_to_u8(0xC0), 0);
+
+    // All posit8 and posit16 values.
+    for p in 0..=u16::MAX {
+        let r = posit_to_f64_reference(p.into(), 16);
+        let f = f64::from_bits(soft::posit16_to_f64(p));
+        assert!(f == r || f.is_nan() && r.is_nan(), "{:x}", p);
+        assert_eq!(soft::posit16_to_f32(p), (r as f32).to_bits(), "{:x}", p);
+        if p != 0x8000 {
+            assert_eq!(soft::f64_to_posit16(r.to_bits()), p, "{:x}", p);
+            assert_eq!(soft::posit16_to_i64(p), r as i64, "{:x}", p);
+            assert_eq!(soft::posit16_to_u16(p), r as u16, "{:x}", p);
+        }
+        let q = p as u8;
+        let r = posit_to_f64_reference(q.into(), 8);
+        assert!(f64::from_bits(soft::posit8_to_f64(q)) == r || r.is_nan(), "{:x}", q);
+        assert_eq!(soft::posit8_to_f32(q), (r as f32).to_bits(), "{:x}", q);
+        if q != 0x80 {
+            assert_eq!(soft::f32_to_posit8((r as f32).to_bits()), q, "{:x}", q);
+        }
+        assert_eq!(soft::i16_to_posit16(p as i16), f64_to_posit_reference(p as i16 as f64, 16) as u16, "{}", p as i16);
+        assert_eq!(soft::u16_to_posit8(p), f64_to_posit_reference(p as f64, 8) as u8, "{}", p);
+    }
+
+    for x in random_u64s(10000) {
+        // Rounding, with the rounding point on all kinds of places.
+        let f = f64::from_bits(x & !(0x7FF << 52) | (((x >> 52 & 0xFF) + 0x380) | x >> 52 & 0x800) << 52);
+        assert_eq!(soft::f64_to_posit32(f.to_bits()), f64_to_posit_reference(f, 32) as u32, "{:e}", f);
+        assert_eq!(soft::f64_to_posit16(f.to_bits()), f64_to_posit_reference(f, 16) as u16, "{:e}", f);
+        assert_eq!(soft::f64_to_posit8(f.to_bits()), f64_to_posit_reference(f, 8) as u8, "{:e}", f);
+        if (f as f32).is_finite() {
+            assert_eq!(soft::f32_to_posit32((f as f32).to_bits()), f64_to_posit_reference(f as f32 as f64, 32) as u32, "{:e}", f);
+        }
+
+        // Decoding.
+        let p = x as u32;
+        let r = posit_to_f64_reference(p.into(), 32);
+        if p != 1 << 31 {
+            assert_eq!(soft::posit32_to_f64(p), r.to_bits(), "{:x}", p);
+            assert_eq!(soft::posit32_to_f32(p), (r as f32).to_bits(), "{:x}", p);
+            assert_eq!(soft::posit32_to_i64(p), r as i64, "{:x}", p);
+            assert_eq!(soft::posit32_to_u128(p), r as u128, "{:x}", p);
+            assert_eq!(soft::f64_to_posit32(r.to_bits()), p, "{:x}", p);
+        }
+
+        // Integers, rounded once.
+        let i = (x >> (x % 64)) as i64;
+        let reference = |n| {
+            let d = i as f64; // Might round, so check the exact integer against the boundaries instead when needed.
+            let p = f64_to_posit_reference(d, n);
+            if d as i128 == i as i128 { p } else { u64::MAX }
+        };
+        let p32 = reference(32);
+        if p32 != u64::MAX {
+            assert_eq!(soft::i64_to_posit32(i), p32 as u32, "{}", i);
+            assert_eq!(soft::i128_to_posit16((i as i128) << 10), soft::f64_to_posit16((((i as i128) << 10) as f64).to_bits()), "{}", i); // Beyond the i64 range.
+        }
+        assert_eq!(soft::u128_to_posit32((x as u128) << 64 | 1), soft::u128_to_posit32((x as u128) << 64 | 2), "{}", x);
+    }
+}
+
+#[test]
+fn test_fixed() {
+    // Known values.
+    assert_eq!(soft::i16_fixed_to_f32(-0x8000, 15), (-1f32).to_bits()); // Q15
+    assert_eq!(soft::i32_fixed_to_f64(0x0001_8000, 16), 1.5f64.to_bits()); // Q16.16
+    assert_eq!(soft::u128_fixed_to_f64(1, 64), 2f64.powi(-64).to_bits()); // Q64.64
+    assert_eq!(soft::u64_fixed_to_f32(!0, 32), 2f32.powi(32).to_bits()); // Q32.32, rounded up.
+    assert_eq!(soft::u128_fixed_to_f32(!0, 0), f32::INFINITY.to_bits());
+    assert_eq!(soft::u128_fixed_to_f32(!0, 1), 2f32.powi(127).to_bits());
+    assert_eq!(soft::u32_fixed_to_f32(3, 150), 2); // Subnormal, rounded up from 1.5.
+    assert_eq!(soft::u32_fixed_to_f32(1, 150), 0); // Half of the smallest subnormal, ties to even.
+    assert_eq!(soft::u32_fixed_to_f64(5, 1076), 1); // 1.25 times the smallest subnormal.
+    assert_eq!(soft::i64_fixed_to_f64(-1, u32::MAX), 1 << 63);
+    assert_eq!(soft::f64_to_i16_fixed(1f64.to_bits(), 15, Round::TiesToEven), i16::MAX); // Saturated.
+    assert_eq!(soft::f64_to_i16_fixed((-1f64).to_bits(), 15, Round::TiesToEven), i16::MIN);
+    assert_eq!(soft::f32_to_i32_fixed(0.75f32.to_bits(), 1, Round::TiesToEven), 2);
+    assert_eq!(soft::f32_to_i32_fixed(0.25f32.to_bits(), 1, Round::TiesToEven), 0);
+    assert_eq!(soft::f32_to_i32_fixed(0.25f32.to_bits(), 1, Round::TiesToAway), 1);
+    assert_eq!(soft::f64_to_i64_fixed((-1e-300f64).to_bits(), 32, Round::TowardNegative), -1);
+    assert_eq!(soft::f64_to_u64_fixed((-1e-300f64).to_bits(), 32, Round::TowardNegative), 0);
+    assert_eq!(soft::f64_to_u128_fixed(1e-300f64.to_bits(), 64, Round::TowardPositive), 1);
+    assert_eq!(soft::f64_to_i128_fixed(f64::NEG_INFINITY.to_bits(), 64, Round::TowardZero), i128::MIN);
+    assert_eq!(soft::f64_to_i128_fixed(f64::NAN.to_bits(), 64, Round::TowardPositive), 0);
+    assert_eq!(soft::f64_to_u32_fixed(1e-300f64.to_bits(), u32::MAX, Round::TiesToEven), u32::MAX);
+    assert_eq!(fast::f64_to_i32_fixed(-2.5, 0, Round::TiesToEven), -2);
+    assert_eq!(fast::f32_to_u16_fixed(-0.25, 8, Round::TowardNegative), 0);
+    assert_eq!(fast::u128_fixed_to_f32(!0, 1), 2f32.powi(127));
+
+    let rounds = [Round::TiesToEven, Round::TiesToAway, Round::TowardZero, Round::TowardPositive, Round::TowardNegative];
+    let round_f64 = |y: f64, round| match round {
+        Round::TiesToEven => y.round_ties_even(),
+        Round::TiesToAway => y.round(),
+        Round::TowardZero => y.trunc(),
+        Round::TowardPositive => y.ceil(),
+        Round::TowardNegative => y.floor(),
+    };
+
+    for x in random_u64s(20000) {
+        let frac = (x >> 57) as u32; // 0 to 127
+        let big = x as u32 >> 22; // 0 to 1023
+        let i = (x >> (x % 64)) as i64;
+        let scale = 2f64.powi(-(frac as i32));
+
+        // From fixed point. Conversion to f64 and scaling are both exact for 32-bit integers.
+        assert_eq!(soft::i32_fixed_to_f64(i as i32, frac), ((i as i32) as f64 * scale).to_bits(), "{} {}", i, frac);
+        assert_eq!(soft::u32_fixed_to_f32(i as u32, frac), (((i as u32) as f64 * scale) as f32).to_bits(), "{} {}", i, frac);
+        assert_eq!(soft::i64_fixed_to_f64(i, frac), ((i as f64) * scale).to_bits(), "{} {}", i, frac);
+        assert_eq!(soft::i16_fixed_to_f64(i as i16, big), ((i as i16) as f64 * 2f64.powi(-(big as i32))).to_bits(), "{} {}", i, big);
+        let u = (x as u128) << (x % 64) | 1;
+        assert_eq!(soft::u128_fixed_to_f64(u, frac), soft::u128_to_f64(u).wrapping_sub((frac as u64) << 52), "{} {}", u, frac);
+        assert_eq!(soft::i128_fixed_to_f32(-(u as i128), frac % 100), soft::i128_to_f32(-(u as i128)) - ((frac % 100) << 23), "{} {}", u, frac);
+
+        // To fixed point.
+        let f = f64::from_bits(x & !(0x7FF << 52) | (((x >> 52 & 0x7F) + 0x3C0) | x >> 52 & 0x800) << 52);
+        for round in rounds {
+            let y = round_f64(f * 2f64.powi(frac as i32), round);
+            assert_eq!(soft::f64_to_i32_fixed(f.to_bits(), frac, round), y as i32, "{} {} {:?}", f, frac, round);
+            assert_eq!(soft::f64_to_u64_fixed(f.to_bits(), frac, round), y as u64, "{} {} {:?}", f, frac, round);
+            assert_eq!(soft::f64_to_i128_fixed(f.to_bits(), frac, round), y as i128, "{} {} {:?}", f, frac, round);
+            assert_eq!(soft::f32_to_i16_fixed((f as f32).to_bits(), frac, round), round_f64(f as f32 as f64 * 2f64.powi(frac as i32), round) as i16, "{} {} {:?}", f, frac, round);
+            assert_eq!(fast::f64_to_i32_fixed(f, frac, round), y as i32, "{} {} {:?}", f, frac, round);
+            assert_eq!(fast::f64_to_u128_fixed(f, frac, round), y as u128, "{} {} {:?}", f, frac, round);
+            assert_eq!(fast::f32_to_i64_fixed(f as f32, frac, round), soft::f32_to_i64_fixed((f as f32).to_bits(), frac, round), "{} {} {:?}", f, frac, round);
+        }
+
+        // Fast and soft agree.
+        for frac in [frac, big, big + 1000] {
+            assert_eq!(fast::i64_fixed_to_f64(i, frac).to_bits(), soft::i64_fixed_to_f64(i, frac), "{} {}", i, frac);
+            assert_eq!(fast::u64_fixed_to_f32(i as u64, frac).to_bits(), soft::u64_fixed_to_f32(i as u64, frac), "{} {}", i, frac);
+            assert_eq!(fast::i128_fixed_to_f64(-(u as i128), frac).to_bits(), soft::i128_fixed_to_f64(-(u as i128), frac), "{} {}", u, frac);
+            assert_eq!(fast::f64_to_i16_fixed(f, frac, Round::TiesToAway), soft::f64_to_i16_fixed(f.to_bits(), frac, Round::TiesToAway), "{} {}", f, frac);
+        }
+    }
+}
+
+/// The exact decimal representation of x × 2^exp, which Rust's correctly
+/// rounded float parsing can be used on as a reference.
+fn scaled_to_decimal(x: u128, exp: i32) -> String {
+    let mut limbs = vec![]; // Base 10^9, least significant first.
+    let mut y = x;
+    while y != 0 {
+        limbs.push((y % 1_000_000_000) as u64);
+        y /= 1_000_000_000;
+    }
+    let mut multiply = |factor: u64| {
+        let mut carry = 0;
+        for limb in &mut limbs {
+            let z = *limb * factor + carry;
+            *limb = z % 1_000_000_000;
+            carry = z / 1_000_000_000;
+        }
+        while carry != 0 {
+            limbs.push(carry % 1_000_000_000);
+            carry /= 1_000_000_000;
+        }
+    };
+    // x × 2^exp = x × 2^exp, or x × 5^-exp × 10^exp.
+    let (base, mut n) = if exp >= 0 { (2, exp) } else { (5, -exp) };
+    while n > 0 {
+        let k = n.min(13);
+        multiply(u64::pow(base, k as u32));
+        n -= k;
+    }
+    let digits: String = limbs.iter().rev().enumerate().map(|(i, l)| if i == 0 { l.to_string() } else { format!("{:09}", l) }).collect();
+    format!("{}e{}", if digits.is_empty() { "0" } else { &digits }, exp.min(0))
+}
+
+#[test]
+fn test_scaled() {
+    // Known values.
+    assert_eq!(soft::u64_to_f64_scaled(1, -1074), 1);
+    assert_eq!(soft::u64_to_f64_scaled(1, -1075), 0); // Tie, to even.
+    assert_eq!(soft::u64_to_f64_scaled(3, -1076), 1);
+    assert_eq!(soft::u8_to_f32_scaled(3, -150), 2);
+    assert_eq!(soft::u64_to_f64_scaled(!0, -1086), 1 << 52); // Rounds up to the smallest normal.
+    assert_eq!(soft::u64_to_f64_scaled(!0, 960), 0x7FF << 52); // Rounds up to infinity.
+    assert_eq!(soft::u64_to_f64_scaled(!0 >> 11, 971), f64::MAX.to_bits());
+    assert_eq!(soft::i32_to_f32_scaled(-1, 127), (-2f32.powi(127)).to_bits());
+    assert_eq!(soft::i32_to_f32_scaled(-1, 128), f32::NEG_INFINITY.to_bits());
+    assert_eq!(soft::i128_to_f64_scaled(i128::MIN, i32::MIN), 1 << 63);
+    assert_eq!(soft::u128_to_f64_scaled(1, i32::MAX), 0x7FF << 52);
+    assert_eq!(soft::u16_to_f64_scaled(0, 5000), 0);
+    assert_eq!(fast::u128_to_f32_scaled(!0, -1), 2f32.powi(127));
+    assert_eq!(fast::i64_to_f64_scaled(i64::MIN, 959), -2f64.powi(1022));
+
+    for x in random_u64s(2000) {
+        let u = (x as u128) << 64 | x.rotate_left(23) as u128;
+        let u = u >> (x % 128);
+        let bits = 128 - u.leading_zeros() as i32;
+        let exp = match x % 4 {
+            0 => -1074 - bits + (x >> 8) as i32 % 64, // Around the subnormal range of f64.
+            1 => -149 - bits + (x >> 8) as i32 % 32, // Around the subnormal range of f32.
+            2 => 1024 - bits - (x >> 8) as i32 % 4, // Around the overflow of f64.
+            _ => (x >> 8) as i32 % 1100,
+        };
+        let d = scaled_to_decimal(u, exp);
+        let f: f64 = d.parse().unwrap();
+        let g: f32 = d.parse().unwrap();
+        assert_eq!(soft::u128_to_f64_scaled(u, exp), f.to_bits(), "{} {}", u, exp);
+        assert_eq!(soft::u128_to_f32_scaled(u, exp), g.to_bits(), "{} {}", u, exp);
+        if u > 1 {
+            assert_eq!(soft::i128_to_f64_scaled(-((u >> 1) as i128), exp + 1), soft::u128_to_f64_scaled(u >> 1 << 1, exp) | 1 << 63, "{} {}", u, exp);
+        }
+        assert_eq!(fast::u128_to_f64_scaled(u, exp).to_bits(), f.to_bits(), "{} {}", u, exp);
+        assert_eq!(fast::u128_to_f32_scaled(u, exp).to_bits(), g.to_bits(), "{} {}", u, exp);
+        let v = u as u64 >> (x % 64);
+        let d = scaled_to_decimal(v.into(), exp);
+        let f: f64 = d.parse().unwrap();
+        let g: f32 = d.parse().unwrap();
+        assert_eq!(soft::u64_to_f64_scaled(v, exp), f.to_bits(), "{} {}", v, exp);
+        assert_eq!(fast::u64_to_f64_scaled(v, exp).to_bits(), f.to_bits(), "{} {}", v, exp);
+        assert_eq!(soft::u64_to_f32_scaled(v, exp), g.to_bits(), "{} {}", v, exp);
+        assert_eq!(fast::i64_to_f32_scaled(v as i64, exp).to_bits(), soft::i64_to_f32_scaled(v as i64, exp), "{} {}", v, exp);
+        assert_eq!(fast::u16_to_f64_scaled(v as u16, exp).to_bits(), soft::u16_to_f64_scaled(v as u16, exp), "{} {}", v, exp);
+    }
+}
+
+/// Compares m × 2^e with num / den exactly, for exponents between −300 and 200.
+fn cmp_ratio(m: u128, e: i32, num: u128, den: u128) -> Ordering {
+    type Wide = [u64; 10];
+    let mul = |a: u128, b: u128| {
+        let mut r: Wide = [0; 10];
+        for (i, &x) in [a as u64, (a >> 64) as u64].iter().enumerate() {
+            let mut carry = 0;
+            for (j, &y) in [b as u64, (b >> 64) as u64].iter().enumerate() {
+                let z = x as u128 * y as u128 + r[i + j] as u128 + carry;
+                r[i + j] = z as u64;
+                carry = z >> 64;
+            }
+            r[i + 2] = carry as u64;
+        }
+        r
+    };
+    let shl = |x: Wide, s: u32| {
+        let mut r: Wide = [0; 10];
+        for (i, &limb) in x.iter().enumerate() {
+            let j = i + (s / 64) as usize;
+            if j < 10 { r[j] |= limb << (s % 64); }
+            if j + 1 < 10 && !s.is_multiple_of(64) { r[j + 1] |= limb >> (64 - s % 64); }
+        }
+        r
+    };
+    // m × den × 2^(e + 300) versus num × 2^300.
+    let a = shl(mul(m, den), (e + 300) as u32);
+    let b = shl(mul(num, 1), 300);
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Checks that the bits of a finite positive float, with `mantissa` bits and
+/// a smallest normal exponent of `emin`, are num / den correctly rounded.
+fn check_ratio(r: u64, mantissa: u32, emin: i32, num: u128, den: u128, round: Round) {
+    let (m, e) = if r >> mantissa == 0 {
+        (r as u128, emin - mantissa as i32)
+    } else {
+        ((r & !(!0 << mantissa) | 1 << mantissa) as u128, (r >> mantissa) as i32 - 1 + emin - mantissa as i32)
+    };
+    let cmp = |m, e| cmp_ratio(m, e, num, den);
+    let (pm, pe) = if m == 1 << mantissa && r >> mantissa > 1 { (2 * m - 1, e - 1) } else { (m - 1, e) }; // Previous value.
+    let ok = match round {
+        Round::TowardZero | Round::TowardNegative => cmp(m, e).is_le() && cmp(m + 1, e).is_gt(),
+        Round::TowardPositive => cmp(pm, pe).is_lt() && cmp(m, e).is_ge(),
+        Round::TiesToEven | Round::TiesToAway => {
+            let (down, up) = (cmp(2 * pm + 1, pe - 1), cmp(2 * m + 1, e - 1));
+            let even = m & 1 == 0;
+            down.is_le() && up.is_ge()
+                && (down.is_ne() || round == Round::TiesToAway || even)
+                && (up.is_ne() || round == Round::TiesToEven && even)
+        }
+    };
+    assert!(ok, "{} / {} = {:x} ({:?})", num, den, r, round);
+}
+
+#[test]
+fn test_ratio() {
+    let rounds = [Round::TiesToEven, Round::TiesToAway, Round::TowardZero, Round::TowardPositive, Round::TowardNegative];
+
+    // Known values.
+    assert_eq!(soft::ratio_u64_to_f64(1, 3, Round::TiesToEven), (1.0f64 / 3.0).to_bits());
+    assert_eq!(soft::ratio_u64_to_f64(1, 3, Round::TowardPositive), (1.0f64 / 3.0).to_bits() + 1);
+    assert_eq!(soft::ratio_i32_to_f32(-2, 3, Round::TowardNegative), (-2.0f32 / 3.0).to_bits()); // Nearest is already rounded away from zero.
+    assert_eq!(soft::ratio_i32_to_f32(-2, 3, Round::TowardZero), (-2.0f32 / 3.0).to_bits() - 1);
+    assert_eq!(soft::ratio_i32_to_f32(-2, -3, Round::TowardZero), (2.0f32 / 3.0).to_bits() - 1);
+    assert_eq!(soft::ratio_u8_to_f64(0, 0, Round::TiesToEven), 0x7FF8 << 48);
+    assert_eq!(soft::ratio_i8_to_f64(-1, 0, Round::TiesToEven), f64::NEG_INFINITY.to_bits());
+    assert_eq!(soft::ratio_i16_to_f32(0, -5, Round::TiesToEven), 0);
+    assert_eq!(soft::ratio_u128_to_f32(!0, 1, Round::TiesToEven), f32::INFINITY.to_bits());
+    assert_eq!(soft::ratio_u128_to_f32(!0, 1, Round::TowardZero), f32::MAX.to_bits());
+    assert_eq!(soft::ratio_i128_to_f32(i128::MIN, 1, Round::TowardPositive), (-2f32.powi(127)).to_bits());
+    assert_eq!(soft::ratio_u128_to_f32(1, !0, Round::TiesToEven), 1 << 21); // 2^-128, subnormal.
+    assert_eq!(soft::ratio_u128_to_f64(3 << 100, 1 << 101, Round::TiesToEven), 1.5f64.to_bits());
+    assert_eq!(soft::ratio_u128_to_f64(!0, !0 - 1, Round::TowardPositive), 1f64.to_bits() + 1);
+    assert_eq!(soft::ratio_u128_to_f64(!0 - 1, !0, Round::TowardPositive), 1f64.to_bits());
+    assert_eq!(soft::ratio_u128_to_f64(!0 - 1, !0, Round::TowardZero), 1f64.to_bits() - 1);
+
+    for x in random_u64s(3000) {
+        let y = x.rotate_left(29);
+        let num = ((x as u128) << 64 | y as u128) >> (x % 128);
+        let den = ((y as u128) << 64 | x as u128) >> (y % 128);
+        if num == 0 || den == 0 { continue; }
+        for round in rounds {
+            let r = soft::ratio_u128_to_f64(num, den, round);
+            check_ratio(r, 52, -1022, num, den, round);
+            let r = soft::ratio_u128_to_f32(num, den, round);
+            if r < 0xFF << 23 && r != f32::MAX.to_bits() {
+                check_ratio(r.into(), 23, -126, num, den, round);
+            }
+            let (a, b) = (num as u64 >> (x % 64), den as u64 >> (y % 64));
+            if a != 0 && b != 0 {
+                check_ratio(soft::ratio_u64_to_f64(a, b, round), 52, -1022, a.into(), b.into(), round);
+            }
+            let (a, b) = (a as i32, -(b as i32));
+            if b != 0 && a != 0 {
+                let negative = (a < 0) != (b < 0);
+                let mirrored = match round {
+                    Round::TowardPositive if negative => Round::TowardNegative,
+                    Round::TowardNegative if negative => Round::TowardPositive,
+                    r => r,
+                };
+                assert_eq!(soft::ratio_i32_to_f32(a, b, round), soft::ratio_u32_to_f32(a.unsigned_abs(), b.unsigned_abs(), mirrored) ^ (negative as u32) << 31, "{} {}", a, b);
+            }
+        }
+        let (a, b) = (num as u32, den as u32);
+        if b != 0 {
+            assert_eq!(soft::ratio_u32_to_f64(a, b, Round::TiesToEven), (a as f64 / b as f64).to_bits());
+            assert_eq!(soft::ratio_u16_to_f32(a as u16, b as u16 | 1, Round::TiesToEven), (a as u16 as f32 / (b as u16 | 1) as f32).to_bits());
+        }
+    }
+}
+
+#[test]
+fn test_decode_encode() {
+    use core::num::FpCategory;
+
+    // Known values.
+    let d = soft::decode_f64((-1.5f64).to_bits());
+    assert_eq!(d, soft::Decoded { sign: true, mantissa: 3 << 51, exponent: -52, class: FpCategory::Normal });
+    let d = soft::decode_f64(1);
+    assert_eq!(d, soft::Decoded { sign: false, mantissa: 1, exponent: -1074, class: FpCategory::Subnormal });
+    let d = soft::decode_f32(f32::NEG_INFINITY.to_bits());
+    assert_eq!(d, soft::Decoded { sign: true, mantissa: 0, exponent: 0, class: FpCategory::Infinite });
+    let d = soft::decode_f32(0x7FC0_0001);
+    assert_eq!(d, soft::Decoded { sign: false, mantissa: 0x40_0001, exponent: 0, class: FpCategory::Nan });
+    assert_eq!(soft::decode_f64(1 << 63).class, FpCategory::Zero);
+    assert_eq!(soft::encode_f64(false, 3, -1), 1.5f64.to_bits());
+    assert_eq!(soft::encode_f64(true, 0, 100), 1 << 63);
+    assert_eq!(soft::encode_f64(false, (1 << 53) + 1, 0), (2f64.powi(53)).to_bits()); // Tie, to even.
+    assert_eq!(soft::encode_f64(false, (1 << 53) + 3, 0), (2f64.powi(53) + 4.0).to_bits()); // Tie, to even.
+    assert_eq!(soft::encode_f64(false, !0, -128), 1f64.to_bits()); // Rounds up.
+    assert_eq!(soft::encode_f64(false, 1, 1024), f64::INFINITY.to_bits());
+    assert_eq!(soft::encode_f32(true, 1, -149), 0x8000_0001);
+    assert_eq!(soft::encode_f32(false, 1, -150), 0);
+
+    for x in random_u64s(100000) {
+        // Round trips.
+        let d = soft::decode_f64(x);
+        let f = f64::from_bits(x);
+        assert_eq!(d.class, f.classify(), "{:x}", x);
+        assert_eq!(d.sign, f.is_sign_negative(), "{:x}", x);
+        if f.is_finite() {
+            assert_eq!(soft::encode_f64(d.sign, d.mantissa.into(), d.exponent.into()), x, "{:x}", x);
+            assert!(d.mantissa >> 53 == 0 && (d.class != FpCategory::Normal || d.mantissa >> 52 == 1), "{:x}", x);
+        } else {
+            assert_eq!(d.mantissa, x & !0 >> 12, "{:x}", x);
+        }
+        let y = x as u32;
+        let d = soft::decode_f32(y);
+        let f = f32::from_bits(y);
+        assert_eq!(d.class, f.classify(), "{:x}", y);
+        if f.is_finite() {
+            assert_eq!(soft::encode_f32(d.sign, d.mantissa.into(), d.exponent.into()), y, "{:x}", y);
+        }
+    }
+
+    // Rounding of wide mantissas.
+    for x in random_u64s(1000) {
+        let m = ((x as u128) << 64 | x.rotate_left(7) as u128) >> (x % 64);
+        let e = (x >> 53) as i32 % 1024 - 76;
+        let d = scaled_to_decimal(m, e);
+        let sign = if x & 1 != 0 { "-" } else { "" };
+        let f: f64 = format!("{}{}", sign, d).parse().unwrap();
+        let g: f32 = format!("{}{}", sign, d).parse().unwrap();
+        assert_eq!(soft::encode_f64(x & 1 != 0, m, e), f.to_bits(), "{} {}", m, e);
+        assert_eq!(soft::encode_f32(x & 1 != 0, m, e), g.to_bits(), "{} {}", m, e);
+    }
+}
+
+/// The decimal representation of a little endian slice of limbs.
+fn limbs_to_decimal(limbs: &[u64]) -> String {
+    let mut limbs = limbs.to_vec();
+    let mut parts = vec![]; // Base 10^9, least significant first.
+    while limbs.iter().any(|&l| l != 0) {
+        let mut rem = 0u128;
+        for l in limbs.iter_mut().rev() {
+            let x = rem << 64 | *l as u128;
+            *l = (x / 1_000_000_000) as u64;
+            rem = x % 1_000_000_000;
+        }
+        parts.push(rem);
+    }
+    let digits: String = parts.iter().rev().enumerate().map(|(i, p)| if i == 0 { p.to_string() } else { format!("{:09}", p) }).collect();
+    if digits.is_empty() { "0".into() } else { digits }
+}
+
+#[test]
+fn test_limbs_to_float() {
+    // Known values.
+    assert_eq!(soft::limbs_to_f64(&[], false), 0);
+    assert_eq!(soft::limbs_to_f64(&[0, 0], true), 1 << 63);
+    assert_eq!(soft::limbs_to_f64(&[1], true), (-1f64).to_bits());
+    assert_eq!(soft::limbs_to_f64(&[0, 1], false), 2f64.powi(64).to_bits());
+    assert_eq!(soft::limbs_to_f64(&[0, 0, 1 << 63], false), 2f64.powi(191).to_bits());
+    assert_eq!(soft::limbs_to_f64(&[0, 0, 1 << 63 | 1 << 10], false), 2f64.powi(191).to_bits()); // Tie, to even.
+    assert_eq!(soft::limbs_to_f64(&[1, 0, 1 << 63 | 1 << 10], false), (2f64.powi(191) + 2f64.powi(139)).to_bits()); // Just above the tie.
+    assert_eq!(soft::limbs_to_f64(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1 << 63], false), 2f64.powi(1023).to_bits());
+    assert_eq!(soft::limbs_to_f64(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], false), f64::INFINITY.to_bits());
+    assert_eq!(soft::limbs_to_f64(&[!0; 16], false), f64::INFINITY.to_bits()); // Rounds up to 2^1024.
+    assert_eq!(soft::limbs_to_f32(&[!0, !0], false), f32::INFINITY.to_bits());
+    assert_eq!(soft::limbs_to_f32(&[!0, 0xFFFF_FF7F_FFFF_FFFF], true), (-f32::MAX).to_bits()); // Just below the tie.
+    assert_eq!(soft::limbs_to_f32(&[0, 0xFFFF_FF80_0000_0000], true), f32::NEG_INFINITY.to_bits()); // Tie, to even.
+    assert_eq!(soft::limbs_to_f32(&[1, 1 << 39 | 1 << 15, 0, 0], false), (2f32.powi(103) + 2f32.powi(80)).to_bits());
+
+    for x in random_u64s(3000) {
+        let mut limbs: Vec<u64> = random_u64s((x % 20) as usize).map(|l| l ^ x).collect();
+        let k = (x >> 8) as usize % (limbs.len() + 1);
+        for l in &mut limbs[..k] {
+            *l = if x & 1 == 0 { 0 } else { !0 }; // Long runs of zeros and ones, for ties and carries.
+        }
+        if let Some(l) = limbs.last_mut() {
+            *l >>= x >> 16 & 63;
+        }
+        let d = limbs_to_decimal(&limbs);
+        let f: f64 = d.parse().unwrap();
+        let g: f32 = d.parse().unwrap();
+        assert_eq!(soft::limbs_to_f64(&limbs, false), f.to_bits(), "{:x?}", limbs);
+        assert_eq!(soft::limbs_to_f32(&limbs, true), (-g).to_bits(), "{:x?}", limbs);
+        // Exactly a tie, or just above it.
+        let tie = [x & 1, 0, (x | 1) << 10 | 1 << 63];
+        let f: f64 = limbs_to_decimal(&tie).parse().unwrap();
+        assert_eq!(soft::limbs_to_f64(&tie, false), f.to_bits(), "{:x?}", tie);
+        let tie = [x & 1, (x | 1) << 39 | 1 << 63];
+        let g: f32 = limbs_to_decimal(&tie).parse().unwrap();
+        assert_eq!(soft::limbs_to_f32(&tie, false), g.to_bits(), "{:x?}", tie);
+        if limbs.len() <= 2 {
+            let u = limbs.iter().rev().fold(0, |u, &l| u << 64 | l as u128);
+            assert_eq!(soft::limbs_to_f64(&limbs, false), soft::u128_to_f64(u), "{:x?}", limbs);
+            assert_eq!(soft::limbs_to_f32(&limbs, false), soft::u128_to_f32(u), "{:x?}", limbs);
+        }
+    }
+}
+
+#[test]
+fn test_float_to_limbs() {
+    use soft::{LimbsError, Sign};
+
+    // Known values.
+    let mut out = [7; 3];
+    assert_eq!(soft::f64_to_limbs((-2.75f64).to_bits(), &mut out), Ok(Sign::Negative));
+    assert_eq!(out, [2, 0, 0]);
+    assert_eq!(soft::f64_to_limbs_rounded((-2.75f64).to_bits(), &mut out, Round::TiesToEven), Ok(Sign::Negative));
+    assert_eq!(out, [3, 0, 0]);
+    assert_eq!(soft::f64_to_limbs((-0.5f64).to_bits(), &mut out), Ok(Sign::Zero));
+    assert_eq!(out, [0, 0, 0]);
+    assert_eq!(soft::f64_to_limbs_rounded(0.5f64.to_bits(), &mut out, Round::TiesToAway), Ok(Sign::Positive));
+    assert_eq!(out, [1, 0, 0]);
+    assert_eq!(soft::f64_to_limbs(2f64.powi(64).to_bits(), &mut out), Ok(Sign::Positive));
+    assert_eq!(out, [0, 1, 0]);
+    assert_eq!(soft::f64_to_limbs(2f64.powi(191).to_bits(), &mut out), Ok(Sign::Positive));
+    assert_eq!(out, [0, 0, 1 << 63]);
+    assert_eq!(soft::f64_to_limbs(2f64.powi(192).to_bits(), &mut out), Err(LimbsError::TooShort));
+    assert_eq!(out, [0, 0, 1 << 63]); // Untouched.
+    assert_eq!(soft::f64_to_limbs(f64::INFINITY.to_bits(), &mut out), Err(LimbsError::NotFinite));
+    assert_eq!(soft::f64_to_limbs(f64::NAN.to_bits(), &mut out), Err(LimbsError::NotFinite));
+    assert_eq!(soft::f64_to_limbs(0, &mut []), Ok(Sign::Zero));
+    assert_eq!(soft::f64_to_limbs(1f64.to_bits(), &mut []), Err(LimbsError::TooShort));
+    let mut out = [0; 16];
+    assert_eq!(soft::f64_to_limbs(f64::MAX.to_bits(), &mut out), Ok(Sign::Positive));
+    assert_eq!(out[15], !0 << 11);
+    assert!(out[..15].iter().all(|&l| l == 0));
+    assert_eq!(soft::f32_to_limbs((-f32::MAX).to_bits(), &mut out[..2]), Ok(Sign::Negative));
+    assert_eq!(out[..2], [0, (!0 >> 40) << 40]);
+    let mut out = [0; 1];
+    assert_eq!(soft::f64_to_limbs_rounded((2f64.powi(64) - 2048.0).to_bits(), &mut out, Round::TowardPositive), Ok(Sign::Positive));
+    assert_eq!(out, [!0 << 11]);
+    assert_eq!(soft::f64_to_limbs_rounded(((1u64 << 52) as f64 - 0.5).to_bits(), &mut out, Round::TowardPositive), Ok(Sign::Positive));
+    assert_eq!(out, [1 << 52]);
+
+    let rounds = [Round::TiesToEven, Round::TiesToAway, Round::TowardZero, Round::TowardPositive, Round::TowardNegative];
+    let mut out = [0; 17];
+    for x in random_u64s(20000) {
+        let f = f64::from_bits(x);
+        for round in rounds {
+            let r = soft::f64_to_limbs_rounded(x, &mut out, round);
+            if !f.is_finite() {
+                assert_eq!(r, Err(LimbsError::NotFinite));
+                continue;
+            }
+            // Round trip through the limbs, which is exact for integers.
+            let g = match round {
+                Round::TiesToEven => f.round_ties_even(),
+                Round::TiesToAway => f.round(),
+                Round::TowardZero => f.trunc(),
+                Round::TowardPositive => f.ceil(),
+                Round::TowardNegative => f.floor(),
+            };
+            let sign = if g == 0.0 { Sign::Zero } else if g < 0.0 { Sign::Negative } else { Sign::Positive };
+            assert_eq!(r, Ok(sign), "{:e}", f);
+            assert_eq!(soft::limbs_to_f64(&out, g < 0.0), g.abs().to_bits() | ((g < 0.0) as u64) << 63, "{:e}", f);
+            if g.abs() < 2f64.powi(127) {
+                assert_eq!(out[0] as u128 | (out[1] as u128) << 64, g.abs() as u128, "{:e}", f);
+            }
+            if g != 0.0 {
+                let needed = ((g.abs().to_bits() >> 52) as usize + 64 - 1023) / 64; // Number of limbs for the integer.
+                assert_eq!(soft::f64_to_limbs_rounded(x, &mut out[..needed - 1], round), Err(LimbsError::TooShort), "{:e}", f);
+                assert_eq!(soft::f64_to_limbs_rounded(x, &mut out[..needed], round), Ok(sign), "{:e}", f);
+            }
+        }
+        let y = (x >> 32) as u32;
+        if f32::from_bits(y).is_finite() {
+            assert_eq!(soft::f32_to_limbs(y, &mut out), soft::f64_to_limbs(soft::f32_to_f64(y), &mut out), "{:x}", y);
+        }
+    }
+}